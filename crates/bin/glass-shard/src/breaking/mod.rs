@@ -0,0 +1,22 @@
+use crate::error::ShardError;
+use crate::transpiler::Transpiler;
+use glass_parser::ast::schema::FieldKeys;
+use glass_parser::compatibility::{Change, check_compatibility};
+use std::path::Path;
+
+pub struct Breaking;
+
+impl Breaking {
+    /// Lists the changes made to the Glass files of `against` in `sources`, whose fields
+    /// are keyed on the wire as given.
+    pub fn check(
+        sources: &Path,
+        against: &Path,
+        field_keys: FieldKeys,
+    ) -> Result<Vec<Change>, ShardError> {
+        let old = Transpiler::load_directory(against)?;
+        let new = Transpiler::load_directory(sources)?;
+
+        Ok(check_compatibility(&old, &new, field_keys))
+    }
+}
@@ -1,19 +1,101 @@
 use crate::error::ShardError;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+use glass_codegen::prelude::{FieldKeys, GeneratorOptions, MapKind};
 use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to a directory containing Glass files
-    #[arg(short, long)]
-    pub sources: PathBuf,
+    #[arg(short, long, required = true)]
+    pub sources: Option<PathBuf>,
 
     /// Path to a directory where Rust files will be generated.
     ///
     /// This will overwrite any file inside the folder, please be sure when running.
-    #[arg(short, long)]
-    pub output: PathBuf,
+    #[arg(short, long, required = true)]
+    pub output: Option<PathBuf>,
+
+    /// Collection used for generated `map<K, V>` fields.
+    #[arg(long, value_enum, default_value_t = MapKindArg::Hash)]
+    pub map_kind: MapKindArg,
+
+    /// How fields of numbered schemas are keyed on the wire.
+    #[arg(long, value_enum, default_value_t = FieldKeysArg::Names)]
+    pub field_keys: FieldKeysArg,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Checks Glass files for changes breaking compatibility with a previous version
+    ///
+    /// Exits with a non-zero status when a wire or source breaking change is found.
+    Breaking {
+        /// Path to a directory containing the new version of the Glass files
+        #[arg(short, long)]
+        sources: PathBuf,
+
+        /// Path to a directory containing the previous version, e.g. exported from a git ref
+        #[arg(short, long)]
+        against: PathBuf,
+
+        /// How fields of numbered schemas are keyed on the wire, as given to generate them.
+        #[arg(long, value_enum, default_value_t = FieldKeysArg::Names)]
+        field_keys: FieldKeysArg,
+    },
+}
+
+impl Cli {
+    pub fn generator_options(&self) -> GeneratorOptions {
+        GeneratorOptions {
+            map_kind: self.map_kind.into(),
+            field_keys: self.field_keys.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub enum MapKindArg {
+    /// std::collections::HashMap
+    Hash,
+    /// std::collections::BTreeMap, for deterministic key ordering
+    Btree,
+}
+
+impl From<MapKindArg> for MapKind {
+    fn from(value: MapKindArg) -> Self {
+        match value {
+            MapKindArg::Hash => MapKind::HashMap,
+            MapKindArg::Btree => MapKind::BTreeMap,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub enum FieldKeysArg {
+    /// Field names, as text strings
+    Names,
+    /// Field numbers, as integers, for schemas declaring them
+    Numbers,
+}
+
+impl From<FieldKeysArg> for FieldKeys {
+    fn from(value: FieldKeysArg) -> Self {
+        match value {
+            FieldKeysArg::Names => FieldKeys::Names,
+            FieldKeysArg::Numbers => FieldKeys::Numbers,
+        }
+    }
 }
 
 /// Checks if a path exists and is a directory.
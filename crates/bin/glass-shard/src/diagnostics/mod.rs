@@ -0,0 +1,123 @@
+use codespan_reporting::diagnostic::{Diagnostic as Report, Label, LabelStyle, Severity};
+use codespan_reporting::files::SimpleFile;
+use codespan_reporting::term;
+use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use glass_parser::compatibility::{Change, Compatibility};
+use glass_parser::diagnostic::{LabelStyle as GlassLabelStyle, Severity as GlassSeverity};
+use glass_parser::prelude::{Diagnostic, Project, ProjectError};
+use std::path::Path;
+
+/// Renders every diagnostic of a project error to stderr, followed by a summary count.
+pub fn emit_project_error(error: &ProjectError) {
+    let diagnostics = error.diagnostics();
+    for diagnostic in &diagnostics {
+        emit(error.path(), diagnostic);
+    }
+
+    emit_summary(&diagnostics);
+}
+
+/// Renders the warnings of a successfully loaded project to stderr, if any.
+pub fn emit_project_warnings(project: &Project) {
+    let mut diagnostics = Vec::new();
+    for (path, warning) in project.warnings() {
+        let diagnostic = Diagnostic::from(warning);
+        emit(Some(path), &diagnostic);
+        diagnostics.push(diagnostic);
+    }
+
+    if !diagnostics.is_empty() {
+        emit_summary(&diagnostics);
+    }
+}
+
+/// Renders compatibility changes to stderr, followed by a count per kind of change.
+pub fn emit_changes(changes: &[Change]) {
+    for change in changes {
+        emit(Some(&change.path), &Diagnostic::from(change));
+    }
+
+    let count = |compatibility: Compatibility| {
+        changes
+            .iter()
+            .filter(|change| change.compatibility == compatibility)
+            .count()
+    };
+    eprintln!(
+        "{} wire breaking, {} source breaking and {} safe {}",
+        count(Compatibility::WireBreaking),
+        count(Compatibility::SourceBreaking),
+        count(Compatibility::Safe),
+        plural(changes.len(), "change")
+    );
+}
+
+fn emit_summary(diagnostics: &[Diagnostic]) {
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == GlassSeverity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    eprintln!(
+        "{} {}, {} {} emitted",
+        errors,
+        plural(errors, "error"),
+        warnings,
+        plural(warnings, "warning")
+    );
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_owned()
+    } else {
+        format!("{word}s")
+    }
+}
+
+/// Renders a diagnostic to stderr.
+///
+/// Labels are only shown when the file they refer to can still be read.
+pub fn emit(path: Option<&Path>, diagnostic: &Diagnostic) {
+    let name = path
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let source = path.and_then(|path| std::fs::read_to_string(path).ok());
+    let has_source = source.is_some();
+    let file = SimpleFile::new(name, source.unwrap_or_default());
+
+    let severity = match diagnostic.severity {
+        GlassSeverity::Error => Severity::Error,
+        GlassSeverity::Warning => Severity::Warning,
+        GlassSeverity::Note => Severity::Note,
+    };
+
+    let mut report = Report::new(severity).with_message(&diagnostic.message);
+    if has_source {
+        let labels = diagnostic
+            .labels
+            .iter()
+            .map(|label| {
+                let style = match label.style {
+                    GlassLabelStyle::Primary => LabelStyle::Primary,
+                    GlassLabelStyle::Secondary => LabelStyle::Secondary,
+                };
+                Label::new(style, (), label.span.start..label.span.end).with_message(&label.message)
+            })
+            .collect();
+        report = report.with_labels(labels);
+    } else if let Some(path) = path {
+        report = report.with_notes(vec![format!("in {}", path.display())]);
+    }
+
+    if let Some(hint) = &diagnostic.hint {
+        report = report.with_notes(vec![format!("hint: {hint}")]);
+    }
+
+    let writer = StandardStream::stderr(ColorChoice::Auto);
+    let config = term::Config::default();
+    if let Err(error) = term::emit(&mut writer.lock(), &config, &file, &report) {
+        eprintln!("{}: {error}", diagnostic.message);
+    }
+}
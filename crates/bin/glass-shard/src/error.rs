@@ -16,4 +16,7 @@ pub enum ShardError {
 
     #[error("A parser error occurred: {0}")]
     Parser(#[from] glass_codegen::prelude::ParserError),
+
+    #[error("A project error occurred: {0}")]
+    Project(#[from] glass_codegen::prelude::ProjectError),
 }
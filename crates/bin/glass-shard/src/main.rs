@@ -1,8 +1,13 @@
-use crate::cli::Cli;
+use crate::breaking::Breaking;
+use crate::cli::{Cli, Command};
 use crate::error::ShardError;
 use clap::Parser;
+use glass_codegen::prelude::FieldKeys;
+use std::path::Path;
 
+mod breaking;
 mod cli;
+mod diagnostics;
 mod error;
 mod transpiler;
 
@@ -11,27 +16,75 @@ fn main() -> color_eyre::Result<()> {
 
     let cli = Cli::parse();
 
-    let result = transpiler::Transpiler::transpile_from_directory(&cli.sources, &cli.output);
+    match &cli.command {
+        Some(Command::Breaking {
+            sources,
+            against,
+            field_keys,
+        }) => breaking(sources, against, (*field_keys).into()),
+        None => {
+            // Both are required by clap whenever no subcommand is given.
+            let (Some(sources), Some(output)) = (&cli.sources, &cli.output) else {
+                unreachable!();
+            };
+
+            transpile(&cli, sources, output);
+        }
+    }
+
+    Ok(())
+}
+
+fn transpile(cli: &Cli, sources: &Path, output: &Path) {
+    let result =
+        transpiler::Transpiler::transpile_from_directory(sources, output, &cli.generator_options());
     match result {
-        Ok(()) => println!("🚀 Transpilation successful!"),
-        Err(error) => match error {
-            ShardError::InvalidPath(path) => {
-                eprintln!("😢 Invalid path detected: {path}");
-            }
-            ShardError::InexistentPath(path) => {
-                eprintln!("😢 Inexistent path detected: {path}");
-            }
-            ShardError::NotDirectory(path) => {
-                eprintln!("😢 Path is not a directory: {path}");
-            }
-            ShardError::GeneralIo(_) => {
-                eprintln!("😭 Unexpected IO error");
-            }
-            ShardError::Parser(error) => {
-                eprintln!("😭 Unexpected Parser error: {error}");
+        Ok(project) => {
+            diagnostics::emit_project_warnings(&project);
+            println!("🚀 Transpilation successful!");
+        }
+        Err(error) => report_error(error),
+    }
+}
+
+fn breaking(sources: &Path, against: &Path, field_keys: FieldKeys) {
+    match Breaking::check(sources, against, field_keys) {
+        Ok(changes) => {
+            diagnostics::emit_changes(&changes);
+            if changes.iter().any(|change| change.is_breaking()) {
+                eprintln!("💔 Breaking changes detected");
+                std::process::exit(1);
             }
-        },
+
+            println!("🤝 No breaking changes detected");
+        }
+        Err(error) => {
+            report_error(error);
+            std::process::exit(1);
+        }
     }
+}
 
-    Ok(())
+fn report_error(error: ShardError) {
+    match error {
+        ShardError::InvalidPath(path) => {
+            eprintln!("😢 Invalid path detected: {path}");
+        }
+        ShardError::InexistentPath(path) => {
+            eprintln!("😢 Inexistent path detected: {path}");
+        }
+        ShardError::NotDirectory(path) => {
+            eprintln!("😢 Path is not a directory: {path}");
+        }
+        ShardError::GeneralIo(_) => {
+            eprintln!("😭 Unexpected IO error");
+        }
+        ShardError::Parser(error) => {
+            eprintln!("😭 Unexpected Parser error: {error}");
+        }
+        ShardError::Project(error) => {
+            diagnostics::emit_project_error(&error);
+            eprintln!("😢 Failed to load the Glass files");
+        }
+    }
 }
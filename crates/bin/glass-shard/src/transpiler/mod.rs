@@ -1,34 +1,44 @@
 use crate::error::ShardError;
-use glass_codegen::prelude::{File, ValidatedFile, generate};
-use std::collections::HashMap;
+use glass_codegen::prelude::{GeneratorOptions, Project, generate_with_options};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
 pub struct Transpiler;
 
 impl Transpiler {
+    /// Returns the loaded project, so that its warnings can be reported.
     pub fn transpile_from_directory(
         input_path: &Path,
         output_path: &Path,
-    ) -> Result<(), ShardError> {
+        options: &GeneratorOptions,
+    ) -> Result<Project, ShardError> {
         // Validate the input path and output path, then extract the flat file hash map.
         crate::cli::check_path(input_path)?;
         Self::prepare_output_directory(output_path)?;
 
         let file_map = Self::build_file_map(input_path)?;
 
-        // Try to parse each file, skipping with a warning any that failed.
-        let validated_files = Self::parse_and_validate_files(&file_map)?;
+        // Parse and validate every file together, so imports can be resolved across them.
+        let project = Self::load_project(&file_map)?;
 
         // Generate sources and output a HashMap which
         // contains the output path and the content to be outputted.
-        let outputs = Self::generate_outputs(output_path, &validated_files, &file_map);
+        let outputs = Self::generate_outputs(output_path, &project, options);
 
         // Save generated sources to disk
         for (output_path, content) in outputs {
             std::fs::write(output_path, content)?;
         }
 
-        Ok(())
+        Ok(project)
+    }
+
+    /// Parses and validates every Glass file of a directory.
+    pub fn load_directory(input_path: &Path) -> Result<Project, ShardError> {
+        crate::cli::check_path(input_path)?;
+        let file_map = Self::build_file_map(input_path)?;
+
+        Self::load_project(&file_map)
     }
 
     fn prepare_output_directory(output_path: &Path) -> Result<(), ShardError> {
@@ -44,8 +54,9 @@ impl Transpiler {
         Ok(())
     }
 
-    fn build_file_map(input_path: &Path) -> Result<HashMap<String, PathBuf>, ShardError> {
-        let mut file_map = HashMap::new();
+    /// Files are sorted by name, so they are always loaded in the same order.
+    fn build_file_map(input_path: &Path) -> Result<BTreeMap<String, PathBuf>, ShardError> {
+        let mut file_map = BTreeMap::new();
         Self::get_file_paths(input_path, &mut file_map)?;
 
         Ok(file_map)
@@ -53,7 +64,7 @@ impl Transpiler {
 
     fn get_file_paths(
         input_path: &Path,
-        file_map: &mut HashMap<String, PathBuf>,
+        file_map: &mut BTreeMap<String, PathBuf>,
     ) -> Result<(), ShardError> {
         // This is safe to unwrap as we previously validated this path exists
         // and is a directory.
@@ -63,11 +74,9 @@ impl Transpiler {
             let entry = entry?;
 
             let file_name = entry.file_name().to_string_lossy().to_string();
-            let file_name = if file_name.ends_with(".glass") {
-                file_name.replace(".glass", ".rs")
-            } else {
+            if !file_name.ends_with(".glass") {
                 continue;
-            };
+            }
 
             let canonical_path = entry
                 .path()
@@ -80,56 +89,39 @@ impl Transpiler {
         Ok(())
     }
 
-    fn parse_and_validate_files(
-        file_map: &HashMap<String, PathBuf>,
-    ) -> Result<Vec<ValidatedFile>, ShardError> {
-        let mut validated_files = vec![];
-
-        for file_path in file_map.values() {
-            let mut file = match File::try_new(file_path.clone()) {
-                Ok(file) => file,
-                Err(_) => {
-                    println!("🤔 The file '{file_path:#?}' failed to be parsed. Skipping...");
-                    continue;
-                }
-            };
-
-            file.try_parse()?;
-
-            let validated_file = match ValidatedFile::validate(file) {
-                Ok(validated) => validated,
-                Err(_) => {
-                    println!("🤔 The file '{file_path:#?}' failed to be validated. Skipping...");
-                    continue;
-                }
-            };
-
-            validated_files.push(validated_file);
-        }
+    fn load_project(file_map: &BTreeMap<String, PathBuf>) -> Result<Project, ShardError> {
+        let paths = file_map.values().cloned().collect::<Vec<_>>();
+        let project = Project::load(&paths)?;
 
-        Ok(validated_files)
+        Ok(project)
     }
 
+    /// Every generated file is a module named after its Glass file, declared by a `mod.rs`
+    /// written next to them, so that they can reach each other through `super`.
+    ///
+    /// Files imported from outside the sources directory are generated as well, as the
+    /// files importing them refer to their modules.
     fn generate_outputs(
         output_path: &Path,
-        validated_files: &[ValidatedFile],
-        file_map: &HashMap<String, PathBuf>,
+        project: &Project,
+        options: &GeneratorOptions,
     ) -> HashMap<PathBuf, String> {
         let mut output_files = HashMap::new();
+        let mut modules = BTreeSet::new();
 
-        for validated_file in validated_files {
-            for (name, path) in file_map {
-                if validated_file.file.path.eq(path) {
-                    let content = generate(validated_file);
-                    let output_path = output_path.join(name);
-
-                    output_files.insert(output_path, content);
-
-                    break;
-                }
-            }
+        for validated_file in &project.files {
+            let module = validated_file.file.module_name();
+            let content = generate_with_options(validated_file, options);
+            output_files.insert(output_path.join(format!("{module}.rs")), content);
+            modules.insert(module);
         }
 
+        let index = modules
+            .iter()
+            .map(|module| format!("pub mod {module};\n"))
+            .collect::<String>();
+        output_files.insert(output_path.join("mod.rs"), index);
+
         output_files
     }
 }
@@ -0,0 +1,271 @@
+pub mod acme {
+    pub mod store {
+        /// Largest page a client may ask for.
+        pub const MAX_PAGE_SIZE: u32 = 500;
+        pub const API_VERSION: &str = "v2";
+        /// A printed book.
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub struct Book {
+            pub title: String,
+            #[serde(default = "Book::default_genre")]
+            pub genre: Genre,
+            pub pages: Option<u32>,
+        }
+        #[allow(deprecated)]
+        impl Book {
+            fn default_genre() -> Genre {
+                Genre::Fiction
+            }
+        }
+        #[allow(deprecated)]
+        impl Default for Book {
+            fn default() -> Self {
+                Self {
+                    title: Default::default(),
+                    genre: Self::default_genre(),
+                    pages: Default::default(),
+                }
+            }
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Book {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    let value = &self.title;
+                    {
+                        if !(1..128).contains(&value.chars().count()) {
+                            errors.push("title", "length must be in 1..128");
+                        }
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+        pub struct Album {
+            pub id: ulid::Ulid,
+            pub tracks: Vec<String>,
+            pub rating: Option<f64>,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Album {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    let value = &self.tracks;
+                    {
+                        if value.is_empty() {
+                            errors.push("tracks", "must not be empty");
+                        }
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        #[serde(
+            bound(
+                serialize = "T: serde::Serialize",
+                deserialize = "T: serde::de::DeserializeOwned"
+            )
+        )]
+        pub struct Page<T> {
+            pub items: Vec<T>,
+            #[serde(default = "Page::<T>::default_size")]
+            pub size: u32,
+        }
+        #[allow(deprecated)]
+        impl<T> Page<T> {
+            fn default_size() -> u32 {
+                20
+            }
+        }
+        #[allow(deprecated)]
+        impl<T> Default for Page<T> {
+            fn default() -> Self {
+                Self {
+                    items: Default::default(),
+                    size: Self::default_size(),
+                }
+            }
+        }
+        #[allow(deprecated)]
+        impl<T> glass_transport::validation::Validate for Page<T>
+        where
+            T: glass_transport::validation::Validate,
+        {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    for (index, item_0) in self.items.iter().enumerate() {
+                        let path_0 = format!("{}[{index}]", "items");
+                        errors.nest(&path_0, item_0.validate());
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+        pub struct Shelf {
+            pub items: Page<Item>,
+            pub next: Option<Box<Shelf>>,
+            pub labels: std::collections::HashMap<String, String>,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Shelf {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    errors.nest("items", self.items.validate());
+                }
+                {
+                    if let Some(item_0) = &self.next {
+                        errors.nest("next", item_0.validate());
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        /// File attached to an item.
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub struct Attachment {
+            pub id: uuid::Uuid,
+            pub content: serde_bytes::ByteBuf,
+            pub uploaded_at: std::time::SystemTime,
+            pub retention: Option<std::time::Duration>,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Attachment {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    let value = &self.content;
+                    {
+                        if !(1..).contains(&value.len()) {
+                            errors.push("content", "length must be in 1..");
+                        }
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+        pub struct Catalog {
+            pub counts: std::collections::HashMap<Genre, u32>,
+            pub attachments: std::collections::HashMap<uuid::Uuid, Attachment>,
+            pub checksums: std::collections::HashMap<
+                serde_bytes::ByteBuf,
+                std::time::SystemTime,
+            >,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Catalog {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    for (key, item_0) in self.attachments.iter() {
+                        let path_0 = format!("{}[{key:?}]", "attachments");
+                        errors.nest(&path_0, item_0.validate());
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+        pub struct Archive {
+            pub catalog: Catalog,
+            pub archived_at: std::time::SystemTime,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Archive {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    errors.nest("catalog", self.catalog.validate());
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(
+            Debug,
+            Copy,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub enum Genre {
+            Fiction,
+            Poetry,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Genre {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                Ok(())
+            }
+        }
+        /// Something sold by the store.
+        #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+        #[serde(tag = "type", content = "value")]
+        pub enum Item {
+            Book(Book),
+            Album(Album),
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Item {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                match self {
+                    Self::Book(payload) => payload.validate(),
+                    Self::Album(payload) => payload.validate(),
+                }
+            }
+        }
+    }
+}
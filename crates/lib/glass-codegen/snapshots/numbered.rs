@@ -0,0 +1,81 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct User {
+    pub id: u64,
+    pub nickname: Option<String>,
+    pub labels: std::collections::BTreeMap<String, String>,
+}
+#[allow(deprecated)]
+impl glass_transport::validation::Validate for User {
+    fn validate(&self) -> Result<(), glass_transport::validation::ValidationErrors> {
+        Ok(())
+    }
+}
+#[allow(deprecated)]
+impl serde::Serialize for User {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(3usize))?;
+        map.serialize_entry(&1u32, &self.id)?;
+        map.serialize_entry(&3u32, &self.nickname)?;
+        map.serialize_entry(&4u32, &self.labels)?;
+        map.end()
+    }
+}
+#[allow(deprecated)]
+impl<'de> serde::Deserialize<'de> for User {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor;
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = User;
+            fn expecting(
+                &self,
+                formatter: &mut std::fmt::Formatter,
+            ) -> std::fmt::Result {
+                formatter.write_str("a `User` map keyed by field number")
+            }
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut field_id: Option<u64> = None;
+                let mut field_nickname: Option<String> = None;
+                let mut field_labels: Option<
+                    std::collections::BTreeMap<String, String>,
+                > = None;
+                while let Some(key) = map.next_key::<u32>()? {
+                    match key {
+                        1u32 => field_id = Some(map.next_value()?),
+                        3u32 => field_nickname = map.next_value()?,
+                        4u32 => field_labels = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(User {
+                    id: field_id.ok_or_else(|| serde::de::Error::missing_field("id"))?,
+                    nickname: field_nickname,
+                    labels: field_labels
+                        .ok_or_else(|| serde::de::Error::missing_field("labels"))?,
+                })
+            }
+        }
+        deserializer.deserialize_map(Visitor)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Unnumbered {
+    pub id: u64,
+}
+#[allow(deprecated)]
+impl glass_transport::validation::Validate for Unnumbered {
+    fn validate(&self) -> Result<(), glass_transport::validation::ValidationErrors> {
+        Ok(())
+    }
+}
@@ -0,0 +1,634 @@
+pub mod acme {
+    pub mod store {
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub struct Order {
+            pub id: u64,
+            pub total: u64,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for Order {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    let value = &self.total;
+                    {
+                        if !(..1000).contains(value) {
+                            errors.push("total", "must be in ..1000");
+                        }
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub struct OutOfStock {
+            pub item: String,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for OutOfStock {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                Ok(())
+            }
+        }
+        /// Arguments of `Orders::place`.
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        pub struct OrdersPlaceRequest {
+            pub item: String,
+            pub quantity: u32,
+        }
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for OrdersPlaceRequest {
+            fn validate(
+                &self,
+            ) -> Result<(), glass_transport::validation::ValidationErrors> {
+                let mut errors = glass_transport::validation::ValidationErrors::default();
+                {
+                    let value = &self.item;
+                    {
+                        if !(1..64).contains(&value.chars().count()) {
+                            errors.push("item", "length must be in 1..64");
+                        }
+                    }
+                }
+                errors.into_result()
+            }
+        }
+        /// Liveness of a service.
+        #[async_trait::async_trait]
+        pub trait Health {
+            /// Fully qualified name identifying this service on the wire.
+            const SERVICE_NAME: &'static str = "acme.store.Health";
+            type Error: Send
+                + Sync
+                + serde::Serialize
+                + serde::de::DeserializeOwned
+                + 'static;
+            async fn ping(&self) -> Result<(), Self::Error>;
+        }
+        /// Client of the `Health` service.
+        ///
+        /// Failures of the service are decoded as `E`, the error of its implementation.
+        pub struct HealthClient<E> {
+            client: glass_transport::client::Client,
+            error: std::marker::PhantomData<fn() -> E>,
+        }
+        impl<E> HealthClient<E> {
+            /// Fully qualified name identifying the service on the wire.
+            pub const SERVICE_NAME: &'static str = "acme.store.Health";
+            pub fn new(client: glass_transport::client::Client) -> Self {
+                Self {
+                    client,
+                    error: std::marker::PhantomData,
+                }
+            }
+        }
+        impl<E> Clone for HealthClient<E> {
+            fn clone(&self) -> Self {
+                Self::new(self.client.clone())
+            }
+        }
+        #[allow(deprecated)]
+        impl<E> HealthClient<E>
+        where
+            E: serde::Serialize + serde::de::DeserializeOwned + Send + 'static,
+        {
+            pub async fn ping(
+                &self,
+            ) -> Result<(), glass_transport::client::error::CallError<E>> {
+                let payload = Vec::new();
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "ping",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+        }
+        /// Server of the `Health` service, dispatching its requests to an implementation of [`Health`].
+        pub struct HealthServer<T> {
+            service: std::sync::Arc<T>,
+        }
+        impl<T> HealthServer<T> {
+            pub fn new(service: T) -> Self {
+                Self {
+                    service: std::sync::Arc::new(service),
+                }
+            }
+        }
+        impl<T> Clone for HealthServer<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    service: self.service.clone(),
+                }
+            }
+        }
+        #[allow(deprecated)]
+        #[async_trait::async_trait]
+        impl<T> glass_transport::server::handler::Handler for HealthServer<T>
+        where
+            T: Health + Send + Sync + 'static,
+        {
+            async fn handle(
+                &self,
+                message: glass_transport::message::Message,
+            ) -> Result<
+                glass_transport::message::Message,
+                glass_transport::server::error::ServerError,
+            > {
+                match message.function() {
+                    Some("ping") => {
+                        let result = <T as Health>::ping(&self.service).await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_service_error,
+                        )
+                    }
+                    _ => {
+                        Err(
+                            glass_transport::server::error::ServerError::Status(
+                                glass_transport::message::status::Status::NoSuchMethod,
+                            ),
+                        )
+                    }
+                }
+            }
+        }
+        /// Orders of the store.
+        #[async_trait::async_trait]
+        pub trait Orders: Health<Error = <Self as Orders>::Error> {
+            /// Fully qualified name identifying this service on the wire.
+            const SERVICE_NAME: &'static str = "acme.store.Orders";
+            type Error: Send
+                + Sync
+                + serde::Serialize
+                + serde::de::DeserializeOwned
+                + 'static;
+            type InputStream<T>: glass_transport::Stream<Item = T>
+                + Send
+                + Sync
+            where
+                T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
+            type OutputStream<T>: glass_transport::Stream<Item = T>
+                + Send
+                + Sync
+            where
+                T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
+            /// Places an order.
+            async fn place(
+                &self,
+                item: String,
+                quantity: u32,
+            ) -> Result<Order, OrdersPlaceError<<Self as Orders>::Error>>;
+            async fn get(&self, request: u64) -> Result<Order, <Self as Orders>::Error>;
+            async fn list(
+                &self,
+                request: Vec<u64>,
+            ) -> Result<Vec<Order>, <Self as Orders>::Error>;
+            async fn restock(
+                &self,
+                request: Vec<Order>,
+            ) -> Result<u64, <Self as Orders>::Error>;
+            async fn watch(
+                &self,
+                request: u64,
+            ) -> Result<<Self as Orders>::OutputStream<Order>, <Self as Orders>::Error>;
+            async fn upload(
+                &self,
+                request: <Self as Orders>::InputStream<Order>,
+            ) -> Result<u64, <Self as Orders>::Error>;
+            #[deprecated]
+            async fn sync(
+                &self,
+                request: <Self as Orders>::InputStream<Order>,
+            ) -> Result<<Self as Orders>::OutputStream<Order>, <Self as Orders>::Error>;
+        }
+        /// Errors declared by `Orders::place`.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum OrdersPlaceError<E> {
+            OutOfStock(OutOfStock),
+            /// Failure of the service, not declared by the function.
+            Service(E),
+        }
+        impl<E> From<OutOfStock> for OrdersPlaceError<E> {
+            fn from(error: OutOfStock) -> Self {
+                Self::OutOfStock(error)
+            }
+        }
+        impl<E> glass_transport::message::error::DeclaredError for OrdersPlaceError<E>
+        where
+            E: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            fn status(&self) -> glass_transport::message::status::Status {
+                use glass_transport::message::status::Status;
+                match self {
+                    Self::OutOfStock(_) => Status::Custom(Status::FIRST_DECLARED),
+                    Self::Service(_) => Status::Internal,
+                }
+            }
+            fn encode(&self) -> Result<Vec<u8>, glass_transport::message::EncodeError> {
+                match self {
+                    Self::OutOfStock(error) => {
+                        glass_transport::message::encode_payload(error)
+                    }
+                    Self::Service(error) => {
+                        glass_transport::message::encode_payload(error)
+                    }
+                }
+            }
+            fn decode(
+                status: glass_transport::message::status::Status,
+                payload: &[u8],
+            ) -> Option<Result<Self, glass_transport::message::DecodeError>> {
+                use glass_transport::message::decode_payload;
+                use glass_transport::message::status::Status;
+                let index = match status {
+                    Status::Internal if payload.is_empty() => return None,
+                    Status::Internal => {
+                        return Some(decode_payload(payload).map(Self::Service));
+                    }
+                    Status::Custom(code) => code.checked_sub(Status::FIRST_DECLARED)?,
+                    _ => return None,
+                };
+                let result = match index {
+                    0 => decode_payload(payload).map(Self::OutOfStock),
+                    _ => return None,
+                };
+                Some(result)
+            }
+        }
+        /// Client of the `Orders` service.
+        ///
+        /// Failures of the service are decoded as `E`, the error of its implementation.
+        pub struct OrdersClient<E> {
+            client: glass_transport::client::Client,
+            error: std::marker::PhantomData<fn() -> E>,
+        }
+        impl<E> OrdersClient<E> {
+            /// Fully qualified name identifying the service on the wire.
+            pub const SERVICE_NAME: &'static str = "acme.store.Orders";
+            pub fn new(client: glass_transport::client::Client) -> Self {
+                Self {
+                    client,
+                    error: std::marker::PhantomData,
+                }
+            }
+        }
+        impl<E> Clone for OrdersClient<E> {
+            fn clone(&self) -> Self {
+                Self::new(self.client.clone())
+            }
+        }
+        #[allow(deprecated)]
+        impl<E> OrdersClient<E>
+        where
+            E: serde::Serialize + serde::de::DeserializeOwned + Send + 'static,
+        {
+            pub async fn ping(
+                &self,
+            ) -> Result<(), glass_transport::client::error::CallError<E>> {
+                let payload = Vec::new();
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "ping",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            /// Places an order.
+            pub async fn place(
+                &self,
+                item: String,
+                quantity: u32,
+            ) -> Result<
+                Order,
+                glass_transport::client::error::CallError<OrdersPlaceError<E>>,
+            > {
+                let payload = glass_transport::message::encode_payload(
+                        &OrdersPlaceRequest {
+                            item,
+                            quantity,
+                        },
+                    )
+                    .map_err(glass_transport::client::error::ClientError::Encoding)?;
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "place",
+                        payload,
+                        <OrdersPlaceError<
+                            E,
+                        > as glass_transport::message::error::DeclaredError>::decode,
+                    )
+                    .await
+            }
+            pub async fn get(
+                &self,
+                request: u64,
+            ) -> Result<Order, glass_transport::client::error::CallError<E>> {
+                let payload = glass_transport::message::encode_payload(&request)
+                    .map_err(glass_transport::client::error::ClientError::Encoding)?;
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "get",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            pub async fn list(
+                &self,
+                request: Vec<u64>,
+            ) -> Result<Vec<Order>, glass_transport::client::error::CallError<E>> {
+                let payload = glass_transport::message::encode_payload(&request)
+                    .map_err(glass_transport::client::error::ClientError::Encoding)?;
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "list",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            pub async fn restock(
+                &self,
+                request: Vec<Order>,
+            ) -> Result<u64, glass_transport::client::error::CallError<E>> {
+                let payload = glass_transport::message::encode_payload(&request)
+                    .map_err(glass_transport::client::error::ClientError::Encoding)?;
+                self.client
+                    .unary(
+                        Self::SERVICE_NAME,
+                        "restock",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            pub async fn watch(
+                &self,
+                request: u64,
+            ) -> Result<
+                glass_transport::client::CallStream<Order, E>,
+                glass_transport::client::error::CallError<E>,
+            > {
+                let payload = glass_transport::message::encode_payload(&request)
+                    .map_err(glass_transport::client::error::ClientError::Encoding)?;
+                self.client
+                    .server_streaming(
+                        Self::SERVICE_NAME,
+                        "watch",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            pub async fn upload(
+                &self,
+                requests: impl glass_transport::Stream<Item = Order> + Send + 'static,
+            ) -> Result<u64, glass_transport::client::error::CallError<E>> {
+                let payload = requests;
+                self.client
+                    .client_streaming(
+                        Self::SERVICE_NAME,
+                        "upload",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+            #[deprecated]
+            pub async fn sync(
+                &self,
+                requests: impl glass_transport::Stream<Item = Order> + Send + 'static,
+            ) -> Result<
+                glass_transport::client::CallStream<Order, E>,
+                glass_transport::client::error::CallError<E>,
+            > {
+                let payload = requests;
+                self.client
+                    .bidirectional_streaming(
+                        Self::SERVICE_NAME,
+                        "sync",
+                        payload,
+                        glass_transport::client::decode_service_error::<E>,
+                    )
+                    .await
+            }
+        }
+        /// Server of the `Orders` service, dispatching its requests to an implementation of [`Orders`].
+        pub struct OrdersServer<T> {
+            service: std::sync::Arc<T>,
+        }
+        impl<T> OrdersServer<T> {
+            pub fn new(service: T) -> Self {
+                Self {
+                    service: std::sync::Arc::new(service),
+                }
+            }
+        }
+        impl<T> Clone for OrdersServer<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    service: self.service.clone(),
+                }
+            }
+        }
+        #[allow(deprecated)]
+        #[async_trait::async_trait]
+        impl<T> glass_transport::server::handler::Handler for OrdersServer<T>
+        where
+            T: Orders + Send + Sync + 'static,
+            <T as Orders>::InputStream<
+                Order,
+            >: From<glass_transport::server::dispatch::Requests<Order>>,
+        {
+            async fn handle(
+                &self,
+                message: glass_transport::message::Message,
+            ) -> Result<
+                glass_transport::message::Message,
+                glass_transport::server::error::ServerError,
+            > {
+                match message.function() {
+                    Some("ping") => {
+                        let result = <T as Health>::ping(&self.service).await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_service_error,
+                        )
+                    }
+                    Some("place") => {
+                        let request: OrdersPlaceRequest = glass_transport::server::dispatch::decode_request(
+                            &message,
+                        )?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                        let result = <T as Orders>::place(
+                                &self.service,
+                                request.item,
+                                request.quantity,
+                            )
+                            .await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_declared_error,
+                        )
+                    }
+                    Some("get") => {
+                        let request: u64 = glass_transport::server::dispatch::decode_request(
+                            &message,
+                        )?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                        let result = <T as Orders>::get(&self.service, request).await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_service_error,
+                        )
+                    }
+                    Some("list") => {
+                        let request: Vec<u64> = glass_transport::server::dispatch::decode_request(
+                            &message,
+                        )?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                        let result = <T as Orders>::list(&self.service, request).await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_service_error,
+                        )
+                    }
+                    Some("restock") => {
+                        let request: Vec<Order> = glass_transport::server::dispatch::decode_request(
+                            &message,
+                        )?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                        let result = <T as Orders>::restock(&self.service, request)
+                            .await;
+                        glass_transport::server::dispatch::respond(
+                            &message,
+                            result,
+                            glass_transport::server::dispatch::encode_service_error,
+                        )
+                    }
+                    _ => {
+                        Err(
+                            glass_transport::server::error::ServerError::Status(
+                                glass_transport::message::status::Status::NoSuchMethod,
+                            ),
+                        )
+                    }
+                }
+            }
+            fn handle_call(
+                &self,
+                requests: glass_transport::server::handler::RequestStream,
+            ) -> glass_transport::server::handler::ResponseStream<'_> {
+                glass_transport::server::dispatch::handle_streaming(
+                    self,
+                    requests,
+                    &["watch", "upload", "sync"],
+                    move |message, requests| async move {
+                        match message.function() {
+                            Some("watch") => {
+                                let request: u64 = glass_transport::server::dispatch::decode_request(
+                                    &message,
+                                )?;
+                                glass_transport::validation::Validate::validate(&request)?;
+                                let result = <T as Orders>::watch(&self.service, request)
+                                    .await;
+                                Ok(
+                                    glass_transport::server::dispatch::respond_stream(
+                                        message,
+                                        result,
+                                        glass_transport::server::dispatch::encode_service_error,
+                                    ),
+                                )
+                            }
+                            Some("upload") => {
+                                let (requests, failure) = glass_transport::server::dispatch::decode_stream::<
+                                    Order,
+                                >(&message, requests)?;
+                                let result = <T as Orders>::upload(
+                                        &self.service,
+                                        requests.into(),
+                                    )
+                                    .await;
+                                let response = glass_transport::server::dispatch::respond(
+                                    &message,
+                                    result,
+                                    glass_transport::server::dispatch::encode_service_error,
+                                )?;
+                                Ok(
+                                    failure
+                                        .answer(
+                                            glass_transport::server::dispatch::single_response(response),
+                                        ),
+                                )
+                            }
+                            Some("sync") => {
+                                let (requests, failure) = glass_transport::server::dispatch::decode_stream::<
+                                    Order,
+                                >(&message, requests)?;
+                                let result = <T as Orders>::sync(
+                                        &self.service,
+                                        requests.into(),
+                                    )
+                                    .await;
+                                let responses = glass_transport::server::dispatch::respond_stream(
+                                    message,
+                                    result,
+                                    glass_transport::server::dispatch::encode_service_error,
+                                );
+                                Ok(failure.answer(responses))
+                            }
+                            _ => {
+                                Err(
+                                    glass_transport::server::error::ServerError::Status(
+                                        glass_transport::message::status::Status::NoSuchMethod,
+                                    ),
+                                )
+                            }
+                        }
+                    },
+                )
+            }
+        }
+    }
+}
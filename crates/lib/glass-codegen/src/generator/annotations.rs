@@ -0,0 +1,76 @@
+use crate::generator::context::Context;
+use glass_parser::ast::annotation::Annotation;
+use glass_parser::ast::literal::Literal;
+use glass_parser::validator::annotations::{AnnotationTarget, is_known_annotation};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// `#[doc]` attributes carrying the `///` comments of a declaration.
+pub fn generate_docs(docs: &[String]) -> TokenStream {
+    quote! { #(#[doc = #docs])* }
+}
+
+/// Attributes of the item generated for an annotated declaration.
+///
+/// `@deprecated` maps to `#[deprecated]`, while unknown annotations are handed to plugins.
+pub fn generate_attributes(
+    annotations: &[Annotation],
+    target: AnnotationTarget,
+    context: &Context,
+) -> TokenStream {
+    let mut attributes = Vec::new();
+    for annotation in annotations {
+        if annotation.name == "deprecated" {
+            let attribute = match annotation.args.first().map(|arg| &arg.value) {
+                Some(Literal::String(note)) => quote! { #[deprecated(note = #note)] },
+                _ => quote! { #[deprecated] },
+            };
+            attributes.push(attribute);
+        } else if !is_known_annotation(&annotation.name) {
+            attributes.extend(
+                context
+                    .options
+                    .plugins
+                    .iter()
+                    .filter_map(|plugin| plugin.attributes(annotation, target)),
+            );
+        }
+    }
+
+    quote! { #(#attributes)* }
+}
+
+/// Traits requested through `@rust(derive = "..")`, as written.
+pub fn requested_derives(annotations: &[Annotation]) -> impl Iterator<Item = &str> {
+    annotations
+        .iter()
+        .filter(|annotation| annotation.name == "rust")
+        .filter_map(|annotation| annotation.named_arg("derive"))
+        .filter_map(|arg| match &arg.value {
+            Literal::String(value) => Some(value),
+            _ => None,
+        })
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+}
+
+/// `#[derive(..)]` listing `defaults`, followed by anything requested through
+/// `@rust(derive = "..")` that is not already derived.
+pub fn generate_derives(annotations: &[Annotation], defaults: &[&str]) -> TokenStream {
+    let mut derives = defaults
+        .iter()
+        .map(|derive| derive.to_string())
+        .collect::<Vec<_>>();
+
+    for derive in requested_derives(annotations) {
+        if !derives.iter().any(|existing| existing == derive) {
+            derives.push(derive.to_owned());
+        }
+    }
+
+    let derives = derives
+        .iter()
+        .map(|derive| derive.parse::<TokenStream>().unwrap());
+
+    quote! { #[derive(#(#derives),*)] }
+}
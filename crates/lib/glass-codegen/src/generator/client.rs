@@ -0,0 +1,178 @@
+use crate::generator::annotations::{generate_attributes, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::interface::service_name;
+use crate::generator::util::convert_ast_type_to_rust_type;
+use glass_parser::ast::interface::{FunctionParam, FunctionReturn, Interface};
+use glass_parser::validator::annotations::AnnotationTarget;
+use glass_parser::validator::inheritance::InheritedFunction;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Generates the client of an interface, with a method calling each function it serves.
+///
+/// Inherited functions are served by the interface itself, so they are called through
+/// the same client, on the same service.
+pub fn generate_client(interface: &Interface, context: &Context) -> TokenStream {
+    let client_name = format_ident!("{}Client", interface.name);
+    let service_name = service_name(interface, context);
+    let doc = format!(" Client of the `{}` service.", interface.name);
+
+    let methods = context
+        .validated_file
+        .interface_functions(interface)
+        .into_iter()
+        .map(|inherited| generate_method(inherited, context))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[doc = #doc]
+        ///
+        /// Failures of the service are decoded as `E`, the error of its implementation.
+        pub struct #client_name<E> {
+            client: glass_transport::client::Client,
+            error: std::marker::PhantomData<fn() -> E>,
+        }
+
+        impl<E> #client_name<E> {
+            /// Fully qualified name identifying the service on the wire.
+            pub const SERVICE_NAME: &'static str = #service_name;
+
+            pub fn new(client: glass_transport::client::Client) -> Self {
+                Self {
+                    client,
+                    error: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<E> Clone for #client_name<E> {
+            fn clone(&self) -> Self {
+                Self::new(self.client.clone())
+            }
+        }
+
+        #[allow(deprecated)]
+        impl<E> #client_name<E>
+        where
+            E: serde::Serialize + serde::de::DeserializeOwned + Send + 'static,
+        {
+            #(#methods)*
+        }
+    }
+}
+
+fn generate_method(inherited: InheritedFunction<'_>, context: &Context) -> TokenStream {
+    // Types of inherited functions are resolved from the file declaring them.
+    let context = &context.with_origin(inherited.file);
+    let function = inherited.function;
+    let function_name = format_ident!("{}", function.name);
+    let wire_name = &function.name;
+    let rust_type =
+        |ty| -> TokenStream { convert_ast_type_to_rust_type(ty, context).parse().unwrap() };
+
+    // Streams of requests are encoded by the client as they are sent.
+    let (params, payload) = match &function.param {
+        FunctionParam::None => (quote! {}, quote! { let payload = Vec::new(); }),
+        FunctionParam::Simple(ty) => {
+            let ty = rust_type(ty);
+            (quote! { request: #ty }, encode(quote! { &request }))
+        }
+        FunctionParam::Named(request) => {
+            let request_path: TokenStream = context
+                .declared_path(inherited.file, &request.name)
+                .parse()
+                .unwrap();
+            let arg_names = request
+                .fields
+                .iter()
+                .map(|arg| format_ident!("{}", arg.name))
+                .collect::<Vec<_>>();
+            let arg_types = request.fields.iter().map(|arg| rust_type(&arg.ty));
+            (
+                quote! { #(#arg_names: #arg_types),* },
+                encode(quote! { &#request_path { #(#arg_names),* } }),
+            )
+        }
+        FunctionParam::Stream(ty) => {
+            let ty = rust_type(ty);
+            (
+                quote! { requests: impl glass_transport::Stream<Item = #ty> + Send + 'static },
+                quote! { let payload = requests; },
+            )
+        }
+    };
+
+    // Declared errors are decoded from their own status codes, and the failures of
+    // the service from the payload of internal errors.
+    let (error_type, decode_error) = match function.throws.is_empty() {
+        true => (
+            quote! { E },
+            quote! { glass_transport::client::decode_service_error::<E> },
+        ),
+        false => {
+            let error_path: TokenStream = context
+                .declared_path(
+                    inherited.file,
+                    &function.error_name(&inherited.interface.name),
+                )
+                .parse()
+                .unwrap();
+            (
+                quote! { #error_path<E> },
+                quote! { <#error_path<E> as glass_transport::message::error::DeclaredError>::decode },
+            )
+        }
+    };
+
+    let is_streaming_param = matches!(function.param, FunctionParam::Stream(_));
+    let (return_type, call) = match &function.return_type {
+        Some(FunctionReturn::Stream(ty)) => {
+            let ty = rust_type(ty);
+            let call = match is_streaming_param {
+                true => quote! { bidirectional_streaming },
+                false => quote! { server_streaming },
+            };
+            (
+                quote! { glass_transport::client::CallStream<#ty, #error_type> },
+                call,
+            )
+        }
+        Some(FunctionReturn::Simple(ty)) => {
+            let ty = rust_type(ty);
+            (quote! { #ty }, client_call(is_streaming_param))
+        }
+        None => (quote! { () }, client_call(is_streaming_param)),
+    };
+
+    let docs = generate_docs(&function.docs);
+    let attributes =
+        generate_attributes(&function.annotations, AnnotationTarget::Function, context);
+
+    quote! {
+        #docs
+        #attributes
+        pub async fn #function_name(
+            &self,
+            #params
+        ) -> Result<#return_type, glass_transport::client::error::CallError<#error_type>> {
+            #payload
+            self.client
+                .#call(Self::SERVICE_NAME, #wire_name, payload, #decode_error)
+                .await
+        }
+    }
+}
+
+fn client_call(is_streaming_param: bool) -> TokenStream {
+    match is_streaming_param {
+        true => quote! { client_streaming },
+        false => quote! { unary },
+    }
+}
+
+fn encode(value: TokenStream) -> TokenStream {
+    quote! {
+        let payload = glass_transport::message::encode_payload(#value)
+            .map_err(glass_transport::client::error::ClientError::Encoding)?;
+    }
+}
@@ -0,0 +1,39 @@
+use crate::generator::annotations::{generate_attributes, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::defaults::generate_literal;
+use glass_parser::ast::constant::Constant;
+use glass_parser::ast::literal::Literal;
+use glass_parser::ast::types::{PrimitiveType, Type};
+use glass_parser::validator::annotations::AnnotationTarget;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+pub fn generate_constant(constant: &Constant, context: &Context) -> TokenStream {
+    let constant_name = format_ident!("{}", constant.name);
+
+    // Owned strings cannot be built in a const context.
+    let (constant_type, value) = match (&constant.ty, &constant.value) {
+        (PrimitiveType::String, Literal::String(value)) => (quote! { &str }, quote! { #value }),
+        (ty, value) => {
+            let constant_type: TokenStream = crate::generator::util::convert_ast_type_to_rust_type(
+                &Type::Primitive(ty.clone()),
+                context,
+            )
+            .parse()
+            .unwrap();
+            (constant_type, generate_literal(ty, value))
+        }
+    };
+
+    let docs = generate_docs(&constant.docs);
+    let attributes =
+        generate_attributes(&constant.annotations, AnnotationTarget::Constant, context);
+
+    let generated = quote! {
+        #docs
+        #attributes
+        pub const #constant_name: #constant_type = #value;
+    };
+
+    generated
+}
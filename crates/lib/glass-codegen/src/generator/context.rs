@@ -0,0 +1,102 @@
+use crate::generator::options::GeneratorOptions;
+use crate::prelude::*;
+use glass_parser::ast::schema::SchemaRef;
+
+/// State shared by the generators while emitting a single file.
+pub struct Context<'a> {
+    pub options: &'a GeneratorOptions,
+    pub validated_file: &'a ValidatedFile,
+    /// File declaring the items being generated, which differs from the generated file
+    /// for functions inherited from an imported interface.
+    pub origin: &'a ValidatedFile,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(options: &'a GeneratorOptions, validated_file: &'a ValidatedFile) -> Self {
+        Self {
+            options,
+            validated_file,
+            origin: validated_file,
+        }
+    }
+
+    /// Context generating items declared by another file, whose references are resolved
+    /// from that file.
+    pub fn with_origin(&self, origin: &'a ValidatedFile) -> Self {
+        Self {
+            options: self.options,
+            validated_file: self.validated_file,
+            origin,
+        }
+    }
+
+    /// Path from the generated package module to the parent of every generated file.
+    ///
+    /// Each file is a module of its own, which nests one module per package segment.
+    pub fn root_path(&self) -> String {
+        let depth = self
+            .validated_file
+            .file
+            .package
+            .as_ref()
+            .map_or(0, |package| package.segments.len());
+
+        "super::".repeat(depth + 1)
+    }
+
+    /// Rust path to a named type declared by an imported file.
+    pub fn imported_path(&self, import: &ValidatedFile, name: &str) -> String {
+        let module = import.file.module_name();
+
+        let package = import
+            .file
+            .package
+            .as_ref()
+            .map(|package| format!("{}::", package.module_names().join("::")))
+            .unwrap_or_default();
+
+        format!("{}{module}::{package}{name}", self.root_path())
+    }
+
+    /// Rust path to a named type.
+    ///
+    /// Unqualified references are in scope either as local items or through a `use`
+    /// item, while qualified references to imported types are spelled out in full.
+    pub fn schema_ref_path(&self, schema_ref: &SchemaRef) -> String {
+        if !std::ptr::eq(self.origin, self.validated_file) {
+            return match self.origin.resolve(schema_ref) {
+                Ok(resolved) => {
+                    self.declared_path(resolved.import.unwrap_or(self.origin), schema_ref.name())
+                }
+                Err(_) => schema_ref.name().to_owned(),
+            };
+        }
+
+        match self.validated_file.resolve(schema_ref) {
+            Ok(resolved) if schema_ref.package().is_some() => match resolved.import {
+                Some(import) => self.imported_path(import, schema_ref.name()),
+                None => schema_ref.name().to_owned(),
+            },
+            _ => schema_ref.name().to_owned(),
+        }
+    }
+
+    /// Rust path to an item declared by a file, either the generated one or one of the others.
+    pub fn declared_path(&self, file: &ValidatedFile, name: &str) -> String {
+        match file.file.path == self.validated_file.file.path {
+            true => name.to_owned(),
+            false => self.imported_path(file, name),
+        }
+    }
+
+    /// Rust path to the trait of an interface, spelled out in full when it is imported.
+    pub fn interface_ref_path(&self, interface_ref: &SchemaRef) -> String {
+        match self.validated_file.resolve_interface(interface_ref) {
+            Ok(resolved) => match resolved.import {
+                Some(import) => self.imported_path(import, interface_ref.name()),
+                None => interface_ref.name().to_owned(),
+            },
+            Err(_) => interface_ref.name().to_owned(),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::generator::context::Context;
+use crate::generator::schema::generate_type_params;
+use crate::generator::util::convert_ast_field_type_to_rust_type;
+use glass_parser::ast::literal::Literal;
+use glass_parser::ast::schema::{DefaultValue, Schema, SchemaField};
+use glass_parser::ast::types::{PrimitiveType, Type};
+use proc_macro2::{Ident, Literal as LiteralToken, TokenStream};
+use quote::{format_ident, quote};
+
+/// Name of the function returning the default value of a field, e.g. `default_retries`.
+pub fn default_fn_name(field: &SchemaField) -> Ident {
+    format_ident!("default_{}", field.name)
+}
+
+/// `#[serde(default = "...")]` for a field declaring a default, so it is filled in when missing.
+pub fn generate_serde_default(schema: &Schema, field: &SchemaField) -> TokenStream {
+    if field.default.is_none() {
+        return quote! {};
+    }
+
+    let path = format!("{}::{}", default_fn_owner(schema), default_fn_name(field));
+    quote! { #[serde(default = #path)] }
+}
+
+/// Path of the schema declaring the default functions, spelling out the type parameters
+/// of a generic schema as they cannot be inferred from the call alone.
+pub fn default_fn_owner(schema: &Schema) -> String {
+    match schema.is_generic() {
+        true => format!("{}::<{}>", schema.name, schema.type_params.join(", ")),
+        false => schema.name.clone(),
+    }
+}
+
+/// Functions returning the declared defaults of a schema, plus a `Default` impl.
+///
+/// `Default` is only implemented when every field without a declared default has a natural
+/// empty value, as referenced schemas, enums and unions do not implement it. Validation
+/// rejects deriving `Default` on such schemas, which would conflict with this impl.
+pub fn generate_defaults(schema: &Schema, context: &Context) -> TokenStream {
+    let defaulted = schema
+        .fields
+        .iter()
+        .filter_map(|field| {
+            field
+                .default
+                .as_ref()
+                .map(|default| (field, &default.value))
+        })
+        .collect::<Vec<_>>();
+    if defaulted.is_empty() {
+        return quote! {};
+    }
+
+    let schema_name = format_ident!("{}", schema.name);
+    let type_params = generate_type_params(schema);
+    let functions = defaulted.iter().map(|(field, value)| {
+        let fn_name = default_fn_name(field);
+        let field_type: TokenStream =
+            convert_ast_field_type_to_rust_type(&field.ty, &schema.name, context)
+                .parse()
+                .unwrap();
+        let value = generate_default_value(&field.ty, value, context);
+
+        quote! {
+            fn #fn_name() -> #field_type {
+                #value
+            }
+        }
+    });
+
+    let has_default_impl = schema
+        .fields
+        .iter()
+        .all(|field| field.default.is_some() || has_empty_value(&field.ty));
+    let default_impl = if has_default_impl {
+        let inits = schema.fields.iter().map(|field| {
+            let field_name = format_ident!("{}", field.name);
+            match field.default {
+                Some(_) => {
+                    let fn_name = default_fn_name(field);
+                    quote! { #field_name: Self::#fn_name(), }
+                }
+                None => quote! { #field_name: Default::default(), },
+            }
+        });
+
+        quote! {
+            #[allow(deprecated)]
+            impl #type_params Default for #schema_name #type_params {
+                fn default() -> Self {
+                    Self {
+                        #(#inits)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        impl #type_params #schema_name #type_params {
+            #(#functions)*
+        }
+
+        #default_impl
+    }
+}
+
+/// Types whose `Default` is an empty or zero value.
+fn has_empty_value(ty: &Type) -> bool {
+    match ty {
+        Type::Option(_) | Type::Vector(_) | Type::Map(_) => true,
+        Type::Primitive(primitive) => !matches!(
+            primitive,
+            PrimitiveType::Timestamp | PrimitiveType::Ulid | PrimitiveType::Uuid
+        ),
+        Type::Schema(_) | Type::Generic(_) => false,
+    }
+}
+
+/// Rust expression of a default value, which the validator already matched against its type.
+fn generate_default_value(ty: &Type, value: &DefaultValue, context: &Context) -> TokenStream {
+    match (ty, value) {
+        (_, DefaultValue::None) => quote! { None },
+        (Type::Option(option_type), value) => {
+            let value = generate_default_value(&option_type.inner, value, context);
+            quote! { Some(#value) }
+        }
+        (Type::Primitive(primitive), DefaultValue::Literal(literal)) => {
+            generate_literal(primitive, literal)
+        }
+        (Type::Schema(schema_ref), DefaultValue::Variant(variant)) => {
+            let enum_path: TokenStream = context.schema_ref_path(schema_ref).parse().unwrap();
+            let variant = format_ident!("{}", variant);
+            quote! { #enum_path::#variant }
+        }
+        _ => unreachable!("default values are validated against the type of their field"),
+    }
+}
+
+/// Rust expression of a literal, as a value of the given primitive type.
+pub fn generate_literal(primitive: &PrimitiveType, literal: &Literal) -> TokenStream {
+    let is_float = matches!(primitive, PrimitiveType::F32 | PrimitiveType::F64);
+    match literal {
+        Literal::String(value) => quote! { #value.to_owned() },
+        Literal::Bool(value) => quote! { #value },
+        Literal::Integer(value) if is_float => float_token(*value as f64),
+        Literal::Integer(value) => {
+            let abs = LiteralToken::u128_unsuffixed(value.unsigned_abs());
+            match value.is_negative() {
+                true => quote! { -#abs },
+                false => quote! { #abs },
+            }
+        }
+        Literal::LargeInteger(value) if is_float => float_token(*value as f64),
+        Literal::LargeInteger(value) => {
+            let value = LiteralToken::u128_unsuffixed(*value);
+            quote! { #value }
+        }
+        Literal::Float(value) => float_token(*value),
+        Literal::Duration(duration) if duration.subsec_nanos() == 0 => {
+            let secs = LiteralToken::u64_unsuffixed(duration.as_secs());
+            quote! { std::time::Duration::from_secs(#secs) }
+        }
+        Literal::Duration(duration) => {
+            // Durations are written in whole milliseconds at most, so this always fits.
+            let millis = LiteralToken::u64_unsuffixed(duration.as_millis() as u64);
+            quote! { std::time::Duration::from_millis(#millis) }
+        }
+        Literal::Range { .. } => unreachable!("ranges are only valid in constraint annotations"),
+    }
+}
+
+fn float_token(value: f64) -> TokenStream {
+    let abs = LiteralToken::f64_unsuffixed(value.abs());
+    match value.is_sign_negative() {
+        true => quote! { -#abs },
+        false => quote! { #abs },
+    }
+}
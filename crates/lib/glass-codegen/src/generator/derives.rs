@@ -0,0 +1,155 @@
+use crate::generator::context::Context;
+use crate::generator::options::MapKind;
+use crate::prelude::*;
+use glass_parser::ast::schema::{Schema, SchemaRef};
+use glass_parser::ast::types::{PrimitiveType, Type};
+use glass_parser::ast::union::Union;
+use glass_parser::validator::TypeKind;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Traits derived by a schema, leaving out `Eq` and `Hash` when one of its fields does
+/// not implement them, e.g. a float or a hash map.
+pub fn schema_derives(schema: &Schema, context: &Context) -> Vec<&'static str> {
+    let mut checker = Checker::new(context, &schema.name);
+    let comparisons = schema
+        .fields
+        .iter()
+        .fold(Comparisons::ALL, |comparisons, field| {
+            comparisons.and(checker.check_type(&field.ty, context.origin))
+        });
+
+    comparisons.derives()
+}
+
+/// Traits derived by a union, leaving out `Eq` and `Hash` when one of its payloads does
+/// not implement them.
+pub fn union_derives(union: &Union, context: &Context) -> Vec<&'static str> {
+    let mut checker = Checker::new(context, &union.name);
+    let comparisons = union
+        .variants
+        .iter()
+        .fold(Comparisons::ALL, |comparisons, variant| {
+            comparisons.and(checker.check_declaration(&variant.payload, context.origin))
+        });
+
+    comparisons.derives()
+}
+
+/// Comparison traits implemented by every value a declaration holds.
+#[derive(Debug, Copy, Clone)]
+struct Comparisons {
+    eq: bool,
+    hash: bool,
+}
+
+impl Comparisons {
+    const ALL: Self = Self {
+        eq: true,
+        hash: true,
+    };
+    const NONE: Self = Self {
+        eq: false,
+        hash: false,
+    };
+
+    fn and(self, other: Self) -> Self {
+        Self {
+            eq: self.eq && other.eq,
+            hash: self.hash && other.hash,
+        }
+    }
+
+    fn derives(self) -> Vec<&'static str> {
+        let mut derives = vec!["Debug", "Clone", "PartialEq"];
+        if self.eq {
+            derives.push("Eq");
+        }
+        if self.hash {
+            derives.push("Hash");
+        }
+        derives
+    }
+}
+
+/// Walks the types held by a declaration, through the schemas and unions they refer to.
+struct Checker {
+    map_kind: MapKind,
+    /// Declarations already checked, by the path of their file and their name.
+    visited: HashSet<(PathBuf, String)>,
+}
+
+impl Checker {
+    fn new(context: &Context, name: &str) -> Self {
+        let visited = HashSet::from([(context.origin.file.path.clone(), name.to_owned())]);
+        Self {
+            map_kind: context.options.map_kind,
+            visited,
+        }
+    }
+
+    fn check_type(&mut self, ty: &Type, file: &ValidatedFile) -> Comparisons {
+        match ty {
+            Type::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => Comparisons::NONE,
+            Type::Primitive(_) => Comparisons::ALL,
+            Type::Option(option) => self.check_type(&option.inner, file),
+            Type::Vector(vector) => self.check_type(&vector.inner, file),
+            Type::Map(map) => {
+                let entries = self
+                    .check_type(&map.key, file)
+                    .and(self.check_type(&map.value, file));
+                match self.map_kind {
+                    MapKind::HashMap => Comparisons {
+                        eq: entries.eq,
+                        hash: false,
+                    },
+                    MapKind::BTreeMap => entries,
+                }
+            }
+            Type::Schema(schema_ref) => self.check_declaration(schema_ref, file),
+            Type::Generic(generic) => generic.args.iter().fold(
+                self.check_declaration(&generic.schema, file),
+                |comparisons, arg| comparisons.and(self.check_type(arg, file)),
+            ),
+        }
+    }
+
+    fn check_declaration(&mut self, schema_ref: &SchemaRef, file: &ValidatedFile) -> Comparisons {
+        // Type parameters are not declared, their arguments being checked where they are given.
+        let Ok(resolved) = file.resolve(schema_ref) else {
+            return Comparisons::ALL;
+        };
+        let file = resolved.import.unwrap_or(file);
+
+        // Every declaration only needs to be checked once, including along cycles.
+        let name = schema_ref.name();
+        if !self
+            .visited
+            .insert((file.file.path.clone(), name.to_owned()))
+        {
+            return Comparisons::ALL;
+        }
+
+        match resolved.kind {
+            TypeKind::Enum => Comparisons::ALL,
+            TypeKind::Schema => {
+                let schema = &file.schema_map[&SchemaRef::new(name)];
+                schema
+                    .fields
+                    .iter()
+                    .fold(Comparisons::ALL, |comparisons, field| {
+                        comparisons.and(self.check_type(&field.ty, file))
+                    })
+            }
+            TypeKind::Union => {
+                let union = &file.union_map[name];
+                union
+                    .variants
+                    .iter()
+                    .fold(Comparisons::ALL, |comparisons, variant| {
+                        comparisons.and(self.check_declaration(&variant.payload, file))
+                    })
+            }
+        }
+    }
+}
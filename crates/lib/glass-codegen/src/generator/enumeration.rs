@@ -0,0 +1,61 @@
+use crate::generator::annotations::{generate_attributes, generate_derives, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::validate::generate_enum_validate;
+use glass_parser::ast::enumeration::Enum;
+use glass_parser::validator::annotations::AnnotationTarget;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+pub fn generate_enum(enumeration: &Enum, context: &Context) -> TokenStream {
+    let enum_name = format_ident!("{}", enumeration.name);
+
+    let variants = enumeration
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = format_ident!("{}", variant.name);
+            let docs = generate_docs(&variant.docs);
+            let attributes =
+                generate_attributes(&variant.annotations, AnnotationTarget::Variant, context);
+
+            quote! {
+                #docs
+                #attributes
+                #variant_name
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let docs = generate_docs(&enumeration.docs);
+    let attributes = generate_attributes(&enumeration.annotations, AnnotationTarget::Enum, context);
+    let derives = generate_derives(
+        &enumeration.annotations,
+        &[
+            "Debug",
+            "Copy",
+            "Clone",
+            "PartialEq",
+            "Eq",
+            "PartialOrd",
+            "Ord",
+            "Hash",
+            "serde::Serialize",
+            "serde::Deserialize",
+        ],
+    );
+
+    let validate = generate_enum_validate(enumeration);
+
+    let generated = quote! {
+        #docs
+        #derives
+        #attributes
+        pub enum #enum_name {
+            #(#variants,)*
+        }
+
+        #validate
+    };
+
+    generated
+}
@@ -0,0 +1,103 @@
+use crate::generator::context::Context;
+use crate::prelude::*;
+use glass_parser::ast::interface::{FunctionParam, FunctionReturn};
+use glass_parser::ast::schema::SchemaRef;
+use glass_parser::ast::types::Type;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::BTreeSet;
+
+/// Generates a `use` item for every unqualified imported type the file references.
+///
+/// Each Glass file is generated as its own module named after the file, and all of
+/// them are written side by side, so imported types are reached through `super`.
+/// Qualified references are left out, as they are spelled out in full where used.
+pub fn generate_imports(context: &Context) -> Vec<TokenStream> {
+    let validated_file = context.validated_file;
+
+    let mut imported = BTreeSet::new();
+    for schema_ref in collect_schema_refs(validated_file) {
+        if schema_ref.package().is_some() {
+            continue;
+        }
+
+        let Ok(resolved) = validated_file.resolve(&schema_ref) else {
+            continue;
+        };
+
+        let Some(import) = resolved.import else {
+            continue;
+        };
+
+        imported.insert(context.imported_path(import, schema_ref.name()));
+    }
+
+    imported
+        .into_iter()
+        .map(|path| {
+            let path: TokenStream = path.parse().unwrap();
+            quote! {
+                use #path;
+            }
+        })
+        .collect()
+}
+
+fn collect_schema_refs(validated_file: &ValidatedFile) -> Vec<SchemaRef> {
+    let file = &validated_file.file;
+    let mut schema_refs = Vec::new();
+
+    for schema in &file.schemas {
+        for field in &schema.fields {
+            collect_type_schema_refs(&field.ty, &mut schema_refs);
+        }
+    }
+
+    for union in &file.unions {
+        for variant in &union.variants {
+            schema_refs.push(variant.payload.clone());
+        }
+    }
+
+    for interface in &file.interfaces {
+        for function in &interface.functions {
+            match &function.param {
+                FunctionParam::Stream(ty) | FunctionParam::Simple(ty) => {
+                    collect_type_schema_refs(ty, &mut schema_refs)
+                }
+                // Arguments are collected as the fields of the request schema.
+                FunctionParam::Named(_) | FunctionParam::None => (),
+            }
+
+            match &function.return_type {
+                Some(FunctionReturn::Stream(ty) | FunctionReturn::Simple(ty)) => {
+                    collect_type_schema_refs(ty, &mut schema_refs)
+                }
+                None => (),
+            }
+
+            schema_refs.extend(function.throws.iter().cloned());
+        }
+    }
+
+    schema_refs
+}
+
+fn collect_type_schema_refs(ty: &Type, schema_refs: &mut Vec<SchemaRef>) {
+    match ty {
+        Type::Primitive(_) => (),
+        Type::Option(option) => collect_type_schema_refs(&option.inner, schema_refs),
+        Type::Vector(vector) => collect_type_schema_refs(&vector.inner, schema_refs),
+        Type::Map(map) => {
+            collect_type_schema_refs(&map.key, schema_refs);
+            collect_type_schema_refs(&map.value, schema_refs);
+        }
+        Type::Schema(schema_ref) => schema_refs.push(schema_ref.clone()),
+        Type::Generic(generic) => {
+            schema_refs.push(generic.schema.clone());
+            for arg in &generic.args {
+                collect_type_schema_refs(arg, schema_refs);
+            }
+        }
+    }
+}
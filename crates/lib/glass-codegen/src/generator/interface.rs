@@ -1,29 +1,69 @@
+use crate::generator::annotations::{generate_attributes, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::throws::generate_function_errors;
 use glass_parser::ast::interface::{Function, FunctionParam, FunctionReturn, Interface};
+use glass_parser::validator::annotations::AnnotationTarget;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-pub fn generate_interface(interface: &Interface) -> TokenStream {
+pub fn generate_interface(interface: &Interface, context: &Context) -> TokenStream {
     let interface_name = format_ident!("{}", interface.name);
+    let service_name = service_name(interface, context);
     let generated_associated_types = generated_associated_types(&interface.functions);
-    let generated_functions = generate_functions(&interface.functions);
+    let generated_functions = generate_functions(interface, context);
+    let function_errors = generate_function_errors(interface, context);
+
+    // Implementations of the parents are reused, and so are their associated types. They
+    // share the error of the service, as the client decodes every failure with it.
+    let parents = interface
+        .extends
+        .iter()
+        .map(|parent_ref| {
+            let parent = context
+                .interface_ref_path(parent_ref)
+                .parse::<TokenStream>()
+                .unwrap();
+            quote! { #parent<Error = <Self as #interface_name>::Error> }
+        })
+        .collect::<Vec<_>>();
+    let supertraits = (!parents.is_empty()).then(|| quote! { : #(#parents)+* });
+
+    let docs = generate_docs(&interface.docs);
+    let attributes =
+        generate_attributes(&interface.annotations, AnnotationTarget::Interface, context);
 
     let generated = quote! {
+        #docs
+        #attributes
         #[async_trait::async_trait]
-        pub trait #interface_name {
+        pub trait #interface_name #supertraits {
+            /// Fully qualified name identifying this service on the wire.
+            const SERVICE_NAME: &'static str = #service_name;
+
             #(#generated_associated_types)*
 
             #(#generated_functions)*
         }
+
+        #(#function_errors)*
     };
 
     generated
 }
 
+/// Name of the service implementing the interface, qualified by the package of the file.
+pub fn service_name(interface: &Interface, context: &Context) -> String {
+    match &context.validated_file.file.package {
+        Some(package) => package.qualify(&interface.name),
+        None => interface.name.clone(),
+    }
+}
+
 fn generated_associated_types(functions: &[Function]) -> Vec<TokenStream> {
     let mut generated_associated_types = Vec::new();
 
     let error_type = quote! {
-        type Error: Send + Sync + serde::Serialize + serde::Deserialize<'static> + 'static;
+        type Error: Send + Sync + serde::Serialize + serde::de::DeserializeOwned + 'static;
     };
     generated_associated_types.push(error_type);
 
@@ -37,7 +77,7 @@ fn generated_associated_types(functions: &[Function]) -> Vec<TokenStream> {
 
     if has_input_streams {
         let generated = quote! {
-            type InputStream<T>: futures::stream::Stream<Item = T> + Send + Sync
+            type InputStream<T>: glass_transport::Stream<Item = T> + Send + Sync
             where
                 T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
         };
@@ -46,7 +86,7 @@ fn generated_associated_types(functions: &[Function]) -> Vec<TokenStream> {
 
     if has_output_streams {
         let generated = quote! {
-            type OutputStream<T>: futures::stream::Stream<Item = T> + Send + Sync
+            type OutputStream<T>: glass_transport::Stream<Item = T> + Send + Sync
             where
                 T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
         };
@@ -56,93 +96,94 @@ fn generated_associated_types(functions: &[Function]) -> Vec<TokenStream> {
     generated_associated_types
 }
 
-fn generate_functions(functions: &[Function]) -> Vec<TokenStream> {
-    let mut generated_functions = Vec::with_capacity(functions.len());
-    for function in functions {
+fn generate_functions(interface: &Interface, context: &Context) -> Vec<TokenStream> {
+    // Parents declare associated types of the same names, which are then ambiguous
+    // unless qualified with the trait.
+    let interface_name = format_ident!("{}", interface.name);
+    let self_type = match interface.extends.is_empty() {
+        true => quote! { Self },
+        false => quote! { <Self as #interface_name> },
+    };
+
+    let mut generated_functions = Vec::with_capacity(interface.functions.len());
+    for function in &interface.functions {
         let function_name = format_ident!("{}", function.name);
 
         let generated_param = match &function.param {
             FunctionParam::Stream(inner_type) => {
                 let inner_type_name =
-                    crate::generator::util::convert_ast_type_to_rust_type(inner_type);
+                    crate::generator::util::convert_ast_type_to_rust_type(inner_type, context);
                 let inner_type_ident: TokenStream = inner_type_name.parse().unwrap();
                 quote! {
-                    &self, request: Self::InputStream<#inner_type_ident>,
+                    &self, request: #self_type::InputStream<#inner_type_ident>,
                 }
             }
             FunctionParam::Simple(inner) => {
-                let inner_type_name = crate::generator::util::convert_ast_type_to_rust_type(inner);
+                let inner_type_name =
+                    crate::generator::util::convert_ast_type_to_rust_type(inner, context);
                 let inner_type_ident: TokenStream = inner_type_name.parse().unwrap();
                 quote! {
                     &self, request: #inner_type_ident
                 }
             }
+            FunctionParam::Named(request) => {
+                let args = request.fields.iter().map(|arg| {
+                    let arg_name = format_ident!("{}", arg.name);
+                    let arg_type_name =
+                        crate::generator::util::convert_ast_type_to_rust_type(&arg.ty, context);
+                    let arg_type_ident: TokenStream = arg_type_name.parse().unwrap();
+                    quote! { #arg_name: #arg_type_ident }
+                });
+                quote! {
+                    &self, #(#args),*
+                }
+            }
+            FunctionParam::None => quote! { &self },
+        };
+
+        // Functions declaring errors return them along with the failures of the service.
+        let error_type = match function.throws.is_empty() {
+            true => quote! { #self_type::Error },
+            false => {
+                let error_name = format_ident!("{}", function.error_name(&interface.name));
+                quote! { #error_name<#self_type::Error> }
+            }
         };
 
         let generated_return = if let Some(return_type) = &function.return_type {
             match return_type {
                 FunctionReturn::Stream(inner_type) => {
                     let inner_type_name =
-                        crate::generator::util::convert_ast_type_to_rust_type(inner_type);
+                        crate::generator::util::convert_ast_type_to_rust_type(inner_type, context);
                     let inner_type_ident: TokenStream = inner_type_name.parse().unwrap();
                     quote! {
-                        Result<Self::OutputStream<#inner_type_ident>, Self::Error>
+                        Result<#self_type::OutputStream<#inner_type_ident>, #error_type>
                     }
                 }
                 FunctionReturn::Simple(inner_type) => {
                     let inner_type_name =
-                        crate::generator::util::convert_ast_type_to_rust_type(inner_type);
+                        crate::generator::util::convert_ast_type_to_rust_type(inner_type, context);
                     let inner_type_ident: TokenStream = inner_type_name.parse().unwrap();
                     quote! {
-                        Result<#inner_type_ident, Self::Error>
+                        Result<#inner_type_ident, #error_type>
                     }
                 }
             }
         } else {
             quote! {
-                Result<(), Self::Error>
+                Result<(), #error_type>
             }
         };
 
-        let where_clauses = generate_where_clauses(function);
-        let generated = if where_clauses.is_empty() {
-            quote! {
-                async fn #function_name(#generated_param) -> #generated_return;
-            }
-        } else {
-            quote! {
-                async fn #function_name(#generated_param) -> #generated_return
-                where
-                    #(#where_clauses),*;
-            }
-        };
-
-        generated_functions.push(generated);
-    }
-
-    generated_functions
-}
-
-fn generate_where_clauses(function: &Function) -> Vec<TokenStream> {
-    let mut where_clauses = Vec::new();
-
-    // Add bounds for simple parameter types
-    if let FunctionParam::Simple(param_type) = &function.param {
-        let type_name = crate::generator::util::convert_ast_type_to_rust_type(param_type);
-        let type_ident = format_ident!("{}", type_name);
-        where_clauses.push(quote! {
-            #type_ident: serde::Serialize + serde::de::DeserializeOwned + Send + Sync
+        let docs = generate_docs(&function.docs);
+        let attributes =
+            generate_attributes(&function.annotations, AnnotationTarget::Function, context);
+        generated_functions.push(quote! {
+            #docs
+            #attributes
+            async fn #function_name(#generated_param) -> #generated_return;
         });
     }
 
-    // Add bounds for simple return types
-    if let Some(FunctionReturn::Simple(return_type)) = &function.return_type {
-        let type_name = crate::generator::util::convert_ast_type_to_rust_type(return_type);
-        let type_ident = format_ident!("{}", type_name);
-        where_clauses.push(quote! {
-            #type_ident: serde::Serialize + serde::de::DeserializeOwned + Send + Sync
-        });
-    }
-
-    where_clauses
+    generated_functions
 }
@@ -1,26 +1,89 @@
+use crate::generator::context::Context;
 use crate::prelude::*;
-use quote::quote;
+use quote::{format_ident, quote};
 
+mod annotations;
+mod client;
+mod constant;
+mod context;
+mod defaults;
+mod derives;
+mod enumeration;
+mod imports;
 mod interface;
+mod numbered;
+mod options;
+mod plugin;
 mod schema;
+mod server;
+mod throws;
+mod union;
 mod util;
+mod validate;
+
+pub use options::{FieldKeys, GeneratorOptions, MapKind};
+pub use plugin::Plugin;
 
 pub fn generate(validated_file: &ValidatedFile) -> String {
-    let mut generated_code = Vec::new();
-    for schema in validated_file.schema_map.values() {
-        let generated_schema = schema::generate_schema(schema);
+    generate_with_options(validated_file, &GeneratorOptions::default())
+}
+
+pub fn generate_with_options(validated_file: &ValidatedFile, options: &GeneratorOptions) -> String {
+    let context = Context::new(options, validated_file);
+
+    // Declarations are grouped by kind, constants first and interfaces last, each kind
+    // keeping the order of the source so that the output of a file only changes along with it.
+    let file = &validated_file.file;
+    let mut generated_code = imports::generate_imports(&context);
+    for constant in &file.constants {
+        let generated_constant = constant::generate_constant(constant, &context);
+        generated_code.push(generated_constant);
+    }
+
+    for schema in &file.schemas {
+        let generated_schema = schema::generate_schema(schema, &context);
         generated_code.push(generated_schema);
     }
 
-    for interface in validated_file.interface_map.values() {
-        let generated_interface = interface::generate_interface(interface);
+    for enumeration in &file.enums {
+        let generated_enum = enumeration::generate_enum(enumeration, &context);
+        generated_code.push(generated_enum);
+    }
+
+    for union in &file.unions {
+        let generated_union = union::generate_union(union, &context);
+        generated_code.push(generated_union);
+    }
+
+    for interface in &file.interfaces {
+        let generated_interface = interface::generate_interface(interface, &context);
         generated_code.push(generated_interface);
+
+        let generated_client = client::generate_client(interface, &context);
+        generated_code.push(generated_client);
+
+        let generated_server = server::generate_server(interface, &context);
+        generated_code.push(generated_server);
     }
 
-    let generated_code = quote! {
+    let mut generated_code = quote! {
         #(#generated_code)*
     };
 
+    // Every package segment becomes a nested module, innermost last. Modules are generated
+    // per file, so files sharing a package each nest their own modules, which are reached
+    // through the module of the file, e.g. `users::acme::store::User`.
+    if let Some(package) = &file.package {
+        for segment in package.module_names().iter().rev() {
+            let segment = format_ident!("{}", segment);
+            generated_code = quote! {
+                pub mod #segment {
+                    #generated_code
+                }
+            };
+        }
+    }
+
     let syntax_tree = syn::parse2::<syn::File>(generated_code).unwrap();
     prettyplease::unparse(&syntax_tree)
 }
@@ -29,10 +92,14 @@ pub fn generate(validated_file: &ValidatedFile) -> String {
 mod tests {
     use super::*;
     use glass_parser::ast::File;
+    use glass_parser::ast::annotation::Annotation;
     use glass_parser::prelude::ValidatedFile;
+    use glass_parser::validator::annotations::AnnotationTarget;
+    use proc_macro2::TokenStream;
     use std::fs::File as StdFile;
     use std::io::Write;
     use std::path::PathBuf;
+    use std::sync::Arc;
     use tempfile::Builder;
 
     /// Helper to create a named temporary file with specific content.
@@ -83,4 +150,865 @@ mod tests {
 
         cleanup();
     }
+
+    #[test]
+    fn test_generate_source_order() {
+        let content = r#"
+            schema Zebra { id: u64; }
+            schema Apple { id: u64; }
+            schema Mango { id: u64; }
+            enum Yak { Wild; }
+            enum Bison { Plains; }
+            interface Zoo { fn visit(); }
+            interface Farm { fn harvest(); }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_source_order", content);
+
+        // Maps of validated files are seeded at random, so every validation of the same
+        // file iterates them in a different order.
+        let generated_codes = (0..8)
+            .map(|_| {
+                let mut file = File::try_new(path.clone()).unwrap();
+                file.try_parse().unwrap();
+                generate(&ValidatedFile::validate(file).unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert!(generated_codes.windows(2).all(|pair| pair[0] == pair[1]));
+
+        let generated_code = &generated_codes[0];
+        let position = |item: &str| generated_code.find(item).unwrap();
+        assert!(position("pub struct Zebra") < position("pub struct Apple"));
+        assert!(position("pub struct Apple") < position("pub struct Mango"));
+        assert!(position("pub enum Yak") < position("pub enum Bison"));
+        assert!(position("pub trait Zoo") < position("pub trait Farm"));
+
+        cleanup();
+    }
+
+    /// Compares the code generated for each file of `snapshots` against the snapshot next
+    /// to it, which running the test with `GLASS_UPDATE_SNAPSHOTS=1` rewrites instead.
+    #[test]
+    fn test_generate_snapshots() {
+        let snapshots = [
+            ("declarations", GeneratorOptions::default()),
+            ("services", GeneratorOptions::default()),
+            (
+                "numbered",
+                GeneratorOptions {
+                    map_kind: MapKind::BTreeMap,
+                    field_keys: FieldKeys::Numbers,
+                    ..Default::default()
+                },
+            ),
+        ];
+        let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+        let update = std::env::var_os("GLASS_UPDATE_SNAPSHOTS").is_some();
+
+        for (name, options) in snapshots {
+            let mut file = File::try_new(directory.join(format!("{name}.glass"))).unwrap();
+            file.try_parse().unwrap();
+
+            let validated_file = ValidatedFile::validate(file).unwrap();
+            let generated_code = generate_with_options(&validated_file, &options);
+
+            let snapshot_path = directory.join(format!("{name}.rs"));
+            if update {
+                std::fs::write(&snapshot_path, &generated_code).unwrap();
+                continue;
+            }
+
+            let snapshot = std::fs::read_to_string(&snapshot_path).unwrap();
+            let mismatch = generated_code
+                .lines()
+                .zip(snapshot.lines())
+                .position(|(generated, expected)| generated != expected);
+            assert!(
+                generated_code == snapshot,
+                "`{name}.rs` is out of date from line {}, run the test with `GLASS_UPDATE_SNAPSHOTS=1` to update it",
+                mismatch.unwrap_or(generated_code.lines().count().min(snapshot.lines().count()))
+                    + 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_enum() {
+        let content = r#"
+            enum Status {
+                Active;
+                Suspended;
+            }
+
+            schema Account {
+                status: Status;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_enum", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub enum Status"));
+        assert!(generated_code.contains("Active,"));
+        assert!(generated_code.contains("pub status: Status,"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_union() {
+        let content = r#"
+            schema UserCreated { id: u64; }
+            schema UserDeleted { id: u64; }
+
+            union Event {
+                Created(UserCreated);
+                Deleted(UserDeleted);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_union", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub enum Event"));
+        assert!(generated_code.contains(r#"#[serde(tag = "type", content = "value")]"#));
+        assert!(generated_code.contains("Created(UserCreated),"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_function_params() {
+        let content = r#"
+            schema Receipt { id: u64; }
+            schema Locked { reason: string; }
+            schema Empty { balance: u64; }
+
+            interface Accounts {
+                fn transfer(from: u64, to: u64, amount: u64) -> Receipt;
+                fn ping();
+                fn withdraw(u64) -> Receipt throws Locked | Empty;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_named_params", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains(
+            "/// Arguments of `Accounts::transfer`.\n#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]\npub struct AccountsTransferRequest {"
+        ));
+        assert!(generated_code.contains("pub amount: u64,"));
+        assert!(generated_code.contains(
+            "async fn transfer(\n        &self,\n        from: u64,\n        to: u64,\n        amount: u64,\n    ) -> Result<Receipt, Self::Error>"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_container_signatures() {
+        let content = r#"
+            schema Receipt { id: u64; }
+            schema Page<T> { items: vec<T>; }
+
+            interface Accounts {
+                fn receipts(vec<u64>) -> vec<Receipt>;
+                fn page(option<u32>) -> Page<Receipt>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_container_signatures", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains(
+            "async fn receipts(&self, request: Vec<u64>) -> Result<Vec<Receipt>, Self::Error>;"
+        ));
+        assert!(generated_code.contains(
+            "async fn page(&self, request: Option<u32>) -> Result<Page<Receipt>, Self::Error>;"
+        ));
+        assert!(!generated_code.contains("serde::de::DeserializeOwned + Send + Sync"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_interface_extends() {
+        let content = r#"
+            interface Health { fn ping(); }
+            interface Admin { fn reset(); }
+            interface Billing extends Health, Admin {
+                fn charge(u64) -> u64;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_interface_extends", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains(
+            "pub trait Billing: Health<\n        Error = <Self as Billing>::Error,\n    > + Admin<Error = <Self as Billing>::Error> {"
+        ));
+        assert!(generated_code.contains(
+            "async fn charge(&self, request: u64) -> Result<u64, <Self as Billing>::Error>"
+        ));
+        assert!(generated_code.contains("async fn ping(&self) -> Result<(), Self::Error>;"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_client() {
+        let content = r#"
+            package acme.bank;
+
+            schema Receipt { id: u64; }
+            schema Locked { reason: string; }
+
+            interface Health { fn ping(); }
+            interface Accounts extends Health {
+                fn transfer(from: u64, to: u64) -> Receipt;
+                fn withdraw(u64) -> Receipt throws Locked;
+                fn deposit(stream u64) -> stream Receipt;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_client", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub struct AccountsClient<E> {"));
+        assert!(
+            generated_code
+                .contains("pub const SERVICE_NAME: &'static str = \"acme.bank.Accounts\";")
+        );
+        assert!(generated_code.contains("let payload = Vec::new();"));
+        assert!(generated_code.contains("&AccountsTransferRequest {\n                            from,\n                            to,\n                        },"));
+        assert!(
+            generated_code
+                .contains("glass_transport::client::error::CallError<AccountsWithdrawError<E>>")
+        );
+        assert!(generated_code.contains(".bidirectional_streaming("));
+        assert!(generated_code.contains("glass_transport::client::CallStream<Receipt, E>"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_server() {
+        let content = r#"
+            schema Receipt { id: u64; }
+            schema Locked { reason: string; }
+
+            interface Health { fn ping(); }
+            interface Accounts extends Health {
+                fn get(Receipt) -> Receipt;
+                fn withdraw(u64) -> Receipt throws Locked;
+                fn deposit(stream u64) -> u64;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_server", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains(
+            "impl<T> glass_transport::server::handler::Handler for AccountsServer<T>\nwhere\n    T: Accounts + Send + Sync + 'static,"
+        ));
+        assert!(generated_code.contains("let result = <T as Health>::ping(&self.service).await;"));
+        assert!(generated_code.contains(
+            "glass_transport::validation::Validate::validate(&request)?;\n                let result = <T as Accounts>::get(&self.service, request).await;"
+        ));
+        assert!(
+            generated_code.contains("glass_transport::server::dispatch::encode_declared_error,")
+        );
+        assert!(generated_code.contains("&[\"deposit\"],"));
+        assert!(generated_code.contains(
+            "<T as Accounts>::InputStream<\n        u64,\n    >: From<glass_transport::server::dispatch::Requests<u64>>,"
+        ));
+        assert!(generated_code.contains("glass_transport::message::status::Status::NoSuchMethod,"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_generic_schemas() {
+        let content = r#"
+            schema Page<T> {
+                items: vec<T>;
+                size: u32 default 20;
+            }
+
+            schema Node {
+                value: u64;
+                children: Page<Node>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_generic_schemas", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub struct Page<T> {"));
+        assert!(generated_code.contains(r#"serialize = "T: serde::Serialize""#));
+        assert!(generated_code.contains(r#"deserialize = "T: serde::de::DeserializeOwned""#));
+        assert!(generated_code.contains(r#"#[serde(default = "Page::<T>::default_size")]"#));
+        assert!(generated_code.contains("impl<T> Default for Page<T> {"));
+        assert!(generated_code.contains(
+            "impl<T> glass_transport::validation::Validate for Page<T>\nwhere\n    T: glass_transport::validation::Validate,\n{"
+        ));
+        assert!(generated_code.contains("errors.nest(&path_0, item_0.validate());"));
+        assert!(generated_code.contains("pub children: Page<Box<Node>>,"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_recursive_types() {
+        let content = r#"
+            schema Node {
+                value: u64;
+                next: option<Node>;
+                children: vec<Node>;
+            }
+
+            schema Literal { value: i64; }
+            schema Binary { lhs: Expr; rhs: Expr; }
+
+            union Expr {
+                Lit(Literal);
+                Add(Binary);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_recursive", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub next: Option<Box<Node>>,"));
+        assert!(generated_code.contains("pub children: Vec<Node>,"));
+        assert!(generated_code.contains("pub lhs: Box<Expr>,"));
+        assert!(generated_code.contains("Add(Box<Binary>),"));
+        assert!(generated_code.contains("Lit(Literal),"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_field_numbers() {
+        let content = r#"
+            schema User {
+                reserved 2;
+
+                id: u64 = 1;
+                nickname: option<string> = 3;
+            }
+
+            schema Unnumbered { id: u64; }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_field_numbers", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        // Field names are used unless asked otherwise.
+        let generated_code = generate(&validated_file);
+        assert!(!generated_code.contains("impl serde::Serialize for User"));
+
+        let options = GeneratorOptions {
+            field_keys: FieldKeys::Numbers,
+            ..Default::default()
+        };
+        let generated_code = generate_with_options(&validated_file, &options);
+        assert!(generated_code.contains("impl serde::Serialize for User"));
+        assert!(generated_code.contains("map.serialize_entry(&1u32, &self.id)?;"));
+        assert!(generated_code.contains("3u32 => field_nickname = map.next_value()?,"));
+        assert!(!generated_code.contains("impl serde::Serialize for Unnumbered"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_annotations() {
+        #[derive(Debug)]
+        struct Sensitive;
+
+        impl Plugin for Sensitive {
+            fn attributes(
+                &self,
+                annotation: &Annotation,
+                target: AnnotationTarget,
+            ) -> Option<TokenStream> {
+                (annotation.name == "sensitive" && target == AnnotationTarget::Field)
+                    .then(|| quote::quote! { #[serde(skip_serializing)] })
+            }
+        }
+
+        let content = r#"
+            @deprecated("use Account instead")
+            @rust(derive = "Default, Eq")
+            schema User {
+                @sensitive
+                password: string;
+            }
+
+            enum Role {
+                @deprecated
+                Guest;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_annotations", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(generated_code.contains("#[deprecated(note = \"use Account instead\")]"));
+        assert!(generated_code.contains("    serde::Deserialize,\n    Default\n)]"));
+        assert!(generated_code.contains("#[deprecated]\n    Guest"));
+        assert!(!generated_code.contains("skip_serializing"));
+
+        let options = GeneratorOptions {
+            plugins: vec![Arc::new(Sensitive)],
+            ..Default::default()
+        };
+        let generated_code = generate_with_options(&validated_file, &options);
+        assert!(generated_code.contains("#[serde(skip_serializing)]\n    pub password: String"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_docs() {
+        let content = r#"
+            /// A registered user.
+            schema User {
+                /// Unique identifier.
+                id: u64;
+            }
+
+            /// User management.
+            interface Users {
+                /// Finds a user.
+                fn get(User) -> User;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_docs", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(generated_code.contains("/// A registered user.\n#[derive("));
+        assert!(generated_code.contains("    /// Unique identifier.\n    pub id: u64,"));
+        assert!(generated_code.contains("/// User management.\n#[async_trait::async_trait]"));
+        assert!(generated_code.contains("    /// Finds a user.\n    async fn get"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_field_defaults() {
+        let content = r#"
+            enum Role { Guest; Admin; }
+
+            schema Config {
+                retries: u32 default 3;
+                offset: i64 default -42;
+                alias: option<string> default "root";
+                role: Role default Admin;
+                tags: vec<string>;
+            }
+
+            schema Member {
+                role: Role;
+                active: bool default true;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_defaults", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(generated_code.contains("#[serde(default = \"Config::default_retries\")]"));
+        assert!(generated_code.contains("fn default_offset() -> i64 {\n        -42\n    }"));
+        assert!(generated_code.contains("Some(\"root\".to_owned())"));
+        assert!(generated_code.contains("fn default_role() -> Role {\n        Role::Admin\n    }"));
+        assert!(generated_code.contains("impl Default for Config"));
+        assert!(generated_code.contains("tags: Default::default(),"));
+
+        // Enums do not implement `Default`, so neither can a schema requiring one.
+        assert!(generated_code.contains("fn default_active() -> bool"));
+        assert!(!generated_code.contains("impl Default for Member"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_constants() {
+        let content = r#"
+            /// Largest page a client may ask for.
+            const MAX_PAGE_SIZE: u32 = 500;
+            const API_VERSION: string = "v2";
+            const MIN_BALANCE: i64 = -100;
+            const MAX_TOKENS: u128 = 340282366920938463463374607431768211455;
+            const DEFAULT_TIMEOUT: duration = 30s;
+        "#;
+        let (path, cleanup) = create_temp_file("generate_constants", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(generated_code.contains(
+            "/// Largest page a client may ask for.\npub const MAX_PAGE_SIZE: u32 = 500;"
+        ));
+        assert!(generated_code.contains("pub const API_VERSION: &str = \"v2\";"));
+        assert!(generated_code.contains("pub const MIN_BALANCE: i64 = -100;"));
+        assert!(
+            generated_code
+                .contains("pub const MAX_TOKENS: u128 = 340282366920938463463374607431768211455;")
+        );
+        assert!(generated_code.contains(
+            "pub const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_field_constraints() {
+        let content = r#"
+            schema Address {
+                street: string @len(1..=128);
+            }
+
+            schema User {
+                name: string @len(1..64) @pattern("^[a-z]+$");
+                age: u8 @range(0..150);
+                ratio: option<f64> @range(0..1);
+                addresses: vec<Address> @non_empty;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_field_constraints", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(
+            generated_code.contains("impl glass_transport::validation::Validate for Address {")
+        );
+        assert!(generated_code.contains("if !(1..64).contains(&value.chars().count()) {"));
+        assert!(generated_code.contains("errors.push(\"name\", \"length must be in 1..64\");"));
+        assert!(generated_code.contains("if !PATTERN.is_match(value) {"));
+        assert!(generated_code.contains("if !(0..150).contains(value) {"));
+        assert!(generated_code.contains("if let Some(value) = &self.ratio {"));
+        assert!(generated_code.contains("if !(0.0..1.0).contains(value) {"));
+        assert!(generated_code.contains("if value.is_empty() {"));
+        assert!(generated_code.contains("let path_0 = format!(\"{}[{index}]\", \"addresses\");"));
+        assert!(generated_code.contains("errors.nest(&path_0, item_0.validate());"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_map_kind() {
+        let content = r#"
+            schema Labels {
+                values: map<string, string>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_map", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        let generated_code = generate(&validated_file);
+        assert!(generated_code.contains("std::collections::HashMap<String, String>"));
+
+        let options = GeneratorOptions {
+            map_kind: MapKind::BTreeMap,
+            ..Default::default()
+        };
+        let generated_code = generate_with_options(&validated_file, &options);
+        assert!(generated_code.contains("std::collections::BTreeMap<String, String>"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_derives() {
+        let content = r#"
+            schema Point { x: f64; y: f64; }
+            schema Route { stops: vec<option<Point>>; }
+            schema Labels { values: map<string, u32>; }
+            schema Tagged { id: u64; }
+            schema Wrapper<T> { value: T; }
+            schema Wrapped { point: Wrapper<Point>; }
+
+            union Shape {
+                Route(Route);
+                Tagged(Tagged);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_derives", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+
+        // Comparison traits derived by a declaration, leaving out those of serde.
+        let comparisons = |name: &str, generated_code: &str| {
+            let declaration = generated_code.find(&format!(" {name} {{")).unwrap();
+            let derive = generated_code[..declaration].rfind("#[derive(").unwrap();
+            let derives = &generated_code[derive + "#[derive(".len()..declaration];
+            let derives = &derives[..derives.find(")]").unwrap()];
+            derives
+                .split(',')
+                .map(str::trim)
+                .filter(|derive| !derive.starts_with("serde::"))
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        };
+        let none = ["Debug", "Clone", "PartialEq"];
+        let all = ["Debug", "Clone", "PartialEq", "Eq", "Hash"];
+
+        let generated_code = generate(&validated_file);
+        assert_eq!(comparisons("Point", &generated_code), none);
+        assert_eq!(comparisons("Route", &generated_code), none);
+        assert_eq!(comparisons("Tagged", &generated_code), all);
+        assert_eq!(comparisons("Wrapper<T>", &generated_code), all);
+        assert_eq!(comparisons("Wrapped", &generated_code), none);
+        assert_eq!(comparisons("Shape", &generated_code), none);
+        assert_eq!(
+            comparisons("Labels", &generated_code),
+            ["Debug", "Clone", "PartialEq", "Eq"]
+        );
+
+        // Sorted maps can be hashed.
+        let options = GeneratorOptions {
+            map_kind: MapKind::BTreeMap,
+            ..Default::default()
+        };
+        let generated_code = generate_with_options(&validated_file, &options);
+        assert_eq!(comparisons("Labels", &generated_code), all);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_well_known_types() {
+        let content = r#"
+            schema Upload {
+                id: ulid;
+                owner: uuid;
+                content: bytes;
+                created_at: timestamp;
+                ttl: duration;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generate_well_known", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        let generated_code = generate(&validated_file);
+
+        assert!(generated_code.contains("pub id: ulid::Ulid,"));
+        assert!(generated_code.contains("pub owner: uuid::Uuid,"));
+        assert!(generated_code.contains("pub content: serde_bytes::ByteBuf,"));
+        assert!(generated_code.contains("pub created_at: std::time::SystemTime,"));
+        assert!(generated_code.contains("pub ttl: std::time::Duration,"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_generate_imports() {
+        let temp_dir = Builder::new().prefix("generate_imports").tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("users.glass"),
+            "schema User { id: u64; }",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("billing.glass"),
+            r#"
+                import "users.glass";
+
+                schema Invoice {
+                    user: User;
+                    amount: u64;
+                }
+            "#,
+        )
+        .unwrap();
+
+        let billing_path = temp_dir.path().join("billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+        let billing = project.file(&billing_path.canonicalize().unwrap()).unwrap();
+        let generated_code = generate(billing);
+
+        assert!(generated_code.contains("use super::users::User;"));
+        assert!(!generated_code.contains("pub struct User"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_generate_imported_module_names() {
+        let temp_dir = Builder::new()
+            .prefix("generate_imported_module_names")
+            .tempdir()
+            .unwrap();
+        std::fs::write(
+            temp_dir.path().join("user-types.glass"),
+            "schema User { id: u64; }",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("billing.glass"),
+            r#"
+                import "user-types.glass";
+
+                schema Invoice {
+                    user: User;
+                }
+            "#,
+        )
+        .unwrap();
+
+        let billing_path = temp_dir.path().join("billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+        let billing = project.file(&billing_path.canonicalize().unwrap()).unwrap();
+        let generated_code = generate(billing);
+
+        assert!(generated_code.contains("use super::user_types::User;"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_generate_packages() {
+        let temp_dir = Builder::new()
+            .prefix("generate_packages")
+            .tempdir()
+            .unwrap();
+        std::fs::write(
+            temp_dir.path().join("users.glass"),
+            "package acme.users.v1; schema User { id: u64; }",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("billing.glass"),
+            r#"
+                package acme.billing.v1;
+
+                import "users.glass";
+
+                schema Invoice {
+                    user: acme.users.v1.User;
+                }
+
+                interface Billing {
+                    fn charge(Invoice);
+                }
+            "#,
+        )
+        .unwrap();
+
+        let billing_path = temp_dir.path().join("billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+        let billing = project.file(&billing_path.canonicalize().unwrap()).unwrap();
+        let generated_code = generate(billing);
+
+        assert!(generated_code.contains("pub mod acme"));
+        assert!(generated_code.contains("pub mod v1"));
+        assert!(
+            generated_code
+                .contains("pub user: super::super::super::super::users::acme::users::v1::User,")
+        );
+        assert!(
+            generated_code
+                .contains(r#"const SERVICE_NAME: &'static str = "acme.billing.v1.Billing";"#)
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_generate_keyword_packages() {
+        let temp_dir = Builder::new()
+            .prefix("generate_keyword_packages")
+            .tempdir()
+            .unwrap();
+        std::fs::write(
+            temp_dir.path().join("users.glass"),
+            "package acme.async.v1; schema User { id: u64; }",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("billing.glass"),
+            r#"
+                package acme.async.v1;
+
+                import "users.glass";
+
+                schema Invoice {
+                    user: acme.async.v1.User;
+                }
+
+                interface Billing {
+                    fn charge(Invoice);
+                }
+            "#,
+        )
+        .unwrap();
+
+        let billing_path = temp_dir.path().join("billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+        let billing = project.file(&billing_path.canonicalize().unwrap()).unwrap();
+        let generated_code = generate(billing);
+
+        assert!(generated_code.contains("pub mod async_"));
+        assert!(
+            generated_code
+                .contains("pub user: super::super::super::super::users::acme::async_::v1::User,")
+        );
+        assert!(
+            generated_code
+                .contains(r#"const SERVICE_NAME: &'static str = "acme.async.v1.Billing";"#)
+        );
+
+        temp_dir.close().unwrap();
+    }
 }
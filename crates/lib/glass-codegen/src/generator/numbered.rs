@@ -0,0 +1,139 @@
+use crate::generator::context::Context;
+use crate::generator::defaults::{default_fn_name, default_fn_owner};
+use crate::generator::schema::{generate_type_param_bounds, generate_type_params};
+use crate::generator::util::convert_ast_field_type_to_rust_type;
+use glass_parser::ast::schema::Schema;
+use glass_parser::ast::types::Type;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+/// Generates `serde` implementations encoding a schema as a map keyed by field number.
+///
+/// Integer keys are smaller than field names on the wire, and survive renames.
+/// Unknown numbers are skipped while decoding, so peers can add fields freely.
+/// Missing fields take their declared default, and missing optional fields decode as `None`,
+/// as they do with the derived implementation.
+pub fn generate_numbered_serde(schema: &Schema, context: &Context) -> TokenStream {
+    let schema_name = format_ident!("{}", schema.name);
+    let type_params = generate_type_params(schema);
+    let default_owner: TokenStream = default_fn_owner(schema).parse().unwrap();
+    let expecting = format!("a `{}` map keyed by field number", schema.name);
+    let len = schema.fields.len();
+
+    let mut entries = Vec::new();
+    let mut slots = Vec::new();
+    let mut arms = Vec::new();
+    let mut inits = Vec::new();
+    for field in &schema.fields {
+        // The validator guarantees every field is numbered once any of them is.
+        let Some(number) = field.number else {
+            continue;
+        };
+        let number = Literal::u32_suffixed(number);
+        let field_name = format_ident!("{}", field.name);
+        let slot = format_ident!("field_{}", field.name);
+        let field_type: TokenStream =
+            convert_ast_field_type_to_rust_type(&field.ty, &schema.name, context)
+                .parse()
+                .unwrap();
+
+        entries.push(quote! {
+            map.serialize_entry(&#number, &self.#field_name)?;
+        });
+
+        if field.default.is_some() {
+            let fn_name = default_fn_name(field);
+            slots.push(quote! { let mut #slot: Option<#field_type> = None; });
+            arms.push(quote! { #number => #slot = Some(map.next_value()?), });
+            inits.push(quote! { #field_name: #slot.unwrap_or_else(#default_owner::#fn_name), });
+        } else if let Type::Option(_) = field.ty {
+            slots.push(quote! { let mut #slot: #field_type = None; });
+            arms.push(quote! { #number => #slot = map.next_value()?, });
+            inits.push(quote! { #field_name: #slot, });
+        } else {
+            let missing = &field.name;
+            slots.push(quote! { let mut #slot: Option<#field_type> = None; });
+            arms.push(quote! { #number => #slot = Some(map.next_value()?), });
+            inits.push(quote! {
+                #field_name: #slot.ok_or_else(|| serde::de::Error::missing_field(#missing))?,
+            });
+        }
+    }
+
+    // Generic visitors carry their type parameters through a marker.
+    let (visitor, visitor_value) = match schema.is_generic() {
+        true => (
+            quote! { struct Visitor #type_params (std::marker::PhantomData<#schema_name #type_params>); },
+            quote! { Visitor(std::marker::PhantomData) },
+        ),
+        false => (quote! { struct Visitor; }, quote! { Visitor }),
+    };
+    let de_params = match schema.is_generic() {
+        true => {
+            let params = schema
+                .type_params
+                .iter()
+                .map(|param| format_ident!("{}", param));
+            quote! { <'de, #(#params),*> }
+        }
+        false => quote! { <'de> },
+    };
+    let serialize_bounds = generate_type_param_bounds(schema, quote! { serde::Serialize });
+    let deserialize_bounds =
+        generate_type_param_bounds(schema, quote! { serde::de::DeserializeOwned });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #type_params serde::Serialize for #schema_name #type_params #serialize_bounds {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(Some(#len))?;
+                #(#entries)*
+                map.end()
+            }
+        }
+
+        #[allow(deprecated)]
+        impl #de_params serde::Deserialize<'de> for #schema_name #type_params #deserialize_bounds {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                #visitor
+
+                impl #de_params serde::de::Visitor<'de> for Visitor #type_params #deserialize_bounds {
+                    type Value = #schema_name #type_params;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        formatter.write_str(#expecting)
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: serde::de::MapAccess<'de>,
+                    {
+                        #(#slots)*
+                        while let Some(key) = map.next_key::<u32>()? {
+                            match key {
+                                #(#arms)*
+                                _ => {
+                                    map.next_value::<serde::de::IgnoredAny>()?;
+                                }
+                            }
+                        }
+
+                        Ok(#schema_name {
+                            #(#inits)*
+                        })
+                    }
+                }
+
+                deserializer.deserialize_map(#visitor_value)
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::generator::plugin::Plugin;
+pub use glass_parser::ast::schema::FieldKeys;
+use std::sync::Arc;
+
+/// Collection emitted for Glass `map<K, V>` types.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MapKind {
+    #[default]
+    HashMap,
+    /// Keeps entries sorted by key, so encoded payloads are deterministic.
+    BTreeMap,
+}
+
+/// Options that tweak the generated Rust code.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorOptions {
+    pub map_kind: MapKind,
+    pub field_keys: FieldKeys,
+    /// Handlers of the annotations Glass does not know about.
+    pub plugins: Vec<Arc<dyn Plugin>>,
+}
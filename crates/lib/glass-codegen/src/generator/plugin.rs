@@ -0,0 +1,12 @@
+use glass_parser::ast::annotation::Annotation;
+use glass_parser::validator::annotations::AnnotationTarget;
+use proc_macro2::TokenStream;
+
+/// Extension point for annotations Glass does not know about.
+///
+/// Every unknown annotation is handed to each plugin along with the kind of declaration
+/// it is attached to, and the returned tokens are emitted as attributes of the generated
+/// item, e.g. `#[serde(rename = "...")]`.
+pub trait Plugin: std::fmt::Debug + Send + Sync {
+    fn attributes(&self, annotation: &Annotation, target: AnnotationTarget) -> Option<TokenStream>;
+}
@@ -1,30 +1,137 @@
-use crate::prelude::*;
+use crate::generator::annotations::{generate_attributes, generate_derives, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::defaults::{generate_defaults, generate_serde_default};
+use crate::generator::derives::schema_derives;
+use crate::generator::options::FieldKeys;
+use crate::generator::validate::generate_schema_validate;
 use glass_parser::ast::schema::Schema;
+use glass_parser::validator::annotations::AnnotationTarget;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-pub fn generate_schema(schema: &Schema) -> TokenStream {
+pub fn generate_schema(schema: &Schema, context: &Context) -> TokenStream {
     let schema_name = format_ident!("{}", schema.name);
+    let type_params = generate_type_params(schema);
+
+    let is_numbered = context.options.field_keys == FieldKeys::Numbers
+        && schema.fields.iter().any(|field| field.number.is_some());
+    let defaults = generate_defaults(schema, context);
+    let validate = generate_schema_validate(schema, context);
 
     let mut fields = Vec::new();
     for field in &schema.fields {
         let field_name = format_ident!("{}", field.name);
-        let field_type = crate::generator::util::convert_ast_type_to_rust_type(&field.ty);
+        let field_type = crate::generator::util::convert_ast_field_type_to_rust_type(
+            &field.ty,
+            &schema.name,
+            context,
+        );
         let field_type: TokenStream = field_type.parse().unwrap();
 
+        let serde_default = match is_numbered {
+            true => quote! {},
+            false => generate_serde_default(schema, field),
+        };
+        let docs = generate_docs(&field.docs);
+        let attributes = generate_attributes(&field.annotations, AnnotationTarget::Field, context);
+
         let generated = quote! {
+            #docs
+            #attributes
+            #serde_default
             pub #field_name: #field_type,
         };
 
         fields.push(generated);
     }
 
+    let docs = generate_docs(&schema.docs);
+    let attributes = generate_attributes(&schema.annotations, AnnotationTarget::Schema, context);
+    let mut derives = schema_derives(schema, context);
+
+    if is_numbered {
+        let serde_impls = crate::generator::numbered::generate_numbered_serde(schema, context);
+        let derives = generate_derives(&schema.annotations, &derives);
+        return quote! {
+            #docs
+            #derives
+            #attributes
+            pub struct #schema_name #type_params {
+                #(#fields)*
+            }
+
+            #defaults
+
+            #validate
+
+            #serde_impls
+        };
+    }
+
+    derives.extend(["serde::Serialize", "serde::Deserialize"]);
+    let derives = generate_derives(&schema.annotations, &derives);
+    let serde_bound = generate_serde_bound(schema);
     let generated = quote! {
-        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
-        pub struct #schema_name {
+        #docs
+        #derives
+        #serde_bound
+        #attributes
+        pub struct #schema_name #type_params {
             #(#fields)*
         }
+
+        #defaults
+
+        #validate
     };
 
     generated
 }
+
+/// Type parameters of a generic schema, e.g. `<T>`, or nothing for any other schema.
+///
+/// Parameters are declared without bounds, which are added by the impls needing them.
+pub fn generate_type_params(schema: &Schema) -> TokenStream {
+    if !schema.is_generic() {
+        return quote! {};
+    }
+
+    let params = schema
+        .type_params
+        .iter()
+        .map(|param| format_ident!("{}", param));
+    quote! { <#(#params),*> }
+}
+
+/// `where` clause bounding every type parameter of a generic schema by `bound`.
+pub fn generate_type_param_bounds(schema: &Schema, bound: TokenStream) -> TokenStream {
+    if !schema.is_generic() {
+        return quote! {};
+    }
+
+    let params = schema
+        .type_params
+        .iter()
+        .map(|param| format_ident!("{}", param));
+    quote! { where #(#params: #bound),* }
+}
+
+/// Serde bounds of a generic schema, so type arguments are only required to be
+/// serializable, or deserializable, instead of the bounds serde would infer.
+fn generate_serde_bound(schema: &Schema) -> TokenStream {
+    if !schema.is_generic() {
+        return quote! {};
+    }
+
+    let bound = |bound: &str| {
+        schema
+            .type_params
+            .iter()
+            .map(|param| format!("{param}: {bound}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let serialize = bound("serde::Serialize");
+    let deserialize = bound("serde::de::DeserializeOwned");
+    quote! { #[serde(bound(serialize = #serialize, deserialize = #deserialize))] }
+}
@@ -0,0 +1,237 @@
+use crate::generator::context::Context;
+use crate::generator::util::convert_ast_type_to_rust_type;
+use glass_parser::ast::interface::{FunctionParam, FunctionReturn, Interface};
+use glass_parser::validator::inheritance::InheritedFunction;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Generates the server of an interface, a `Handler` dispatching the requests of every
+/// function it serves to an implementation of the interface.
+///
+/// Requests holding a schema or a union are validated before being dispatched, answering
+/// `BadRequest` when they are not valid. Calls to functions taking or returning streams
+/// are answered through `handle_call`, as they span several messages.
+pub fn generate_server(interface: &Interface, context: &Context) -> TokenStream {
+    let interface_name = format_ident!("{}", interface.name);
+    let server_name = format_ident!("{}Server", interface.name);
+    let doc = format!(
+        " Server of the `{0}` service, dispatching its requests to an implementation of [`{0}`].",
+        interface.name
+    );
+
+    let functions = context
+        .validated_file
+        .interface_functions(interface)
+        .into_iter()
+        .map(|inherited| DispatchedFunction::new(inherited, context))
+        .collect::<Vec<_>>();
+
+    let mut stream_bounds = Vec::new();
+    for bound in functions
+        .iter()
+        .filter_map(|function| function.stream_bound.as_ref())
+    {
+        if !stream_bounds
+            .iter()
+            .any(|existing: &TokenStream| existing.to_string() == bound.to_string())
+        {
+            stream_bounds.push(bound.clone());
+        }
+    }
+
+    let unary_arms = functions
+        .iter()
+        .filter(|function| !function.is_streaming)
+        .map(|function| &function.arm);
+    let streaming_arms = functions
+        .iter()
+        .filter(|function| function.is_streaming)
+        .map(|function| &function.arm)
+        .collect::<Vec<_>>();
+    let streaming_names = functions
+        .iter()
+        .filter(|function| function.is_streaming)
+        .map(|function| &function.wire_name)
+        .collect::<Vec<_>>();
+
+    // The following requests are only decoded by functions taking a stream.
+    let requests = match stream_bounds.is_empty() {
+        true => quote! { _requests },
+        false => quote! { requests },
+    };
+    let handle_call = (!streaming_arms.is_empty()).then(|| {
+        quote! {
+            fn handle_call(
+                &self,
+                requests: glass_transport::server::handler::RequestStream,
+            ) -> glass_transport::server::handler::ResponseStream<'_> {
+                glass_transport::server::dispatch::handle_streaming(
+                    self,
+                    requests,
+                    &[#(#streaming_names),*],
+                    move |message, #requests| async move {
+                        match message.function() {
+                            #(#streaming_arms)*
+                            _ => Err(glass_transport::server::error::ServerError::Status(
+                                glass_transport::message::status::Status::NoSuchMethod,
+                            )),
+                        }
+                    },
+                )
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        pub struct #server_name<T> {
+            service: std::sync::Arc<T>,
+        }
+
+        impl<T> #server_name<T> {
+            pub fn new(service: T) -> Self {
+                Self {
+                    service: std::sync::Arc::new(service),
+                }
+            }
+        }
+
+        impl<T> Clone for #server_name<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    service: self.service.clone(),
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        #[async_trait::async_trait]
+        impl<T> glass_transport::server::handler::Handler for #server_name<T>
+        where
+            T: #interface_name + Send + Sync + 'static,
+            #(#stream_bounds,)*
+        {
+            async fn handle(
+                &self,
+                message: glass_transport::message::Message,
+            ) -> Result<glass_transport::message::Message, glass_transport::server::error::ServerError> {
+                match message.function() {
+                    #(#unary_arms)*
+                    _ => Err(glass_transport::server::error::ServerError::Status(
+                        glass_transport::message::status::Status::NoSuchMethod,
+                    )),
+                }
+            }
+
+            #handle_call
+        }
+    }
+}
+
+/// Dispatch of a single function, either answered by `handle` or by `handle_call`.
+struct DispatchedFunction {
+    wire_name: String,
+    is_streaming: bool,
+    arm: TokenStream,
+    /// Bound letting the requests of a `stream` parameter be handed to the implementation.
+    stream_bound: Option<TokenStream>,
+}
+
+impl DispatchedFunction {
+    fn new(inherited: InheritedFunction<'_>, context: &Context) -> Self {
+        // Types of inherited functions are resolved from the file declaring them.
+        let context = &context.with_origin(inherited.file);
+        let function = inherited.function;
+        let function_name = format_ident!("{}", function.name);
+        let wire_name = function.name.clone();
+        let trait_path: TokenStream = context
+            .declared_path(inherited.file, &inherited.interface.name)
+            .parse()
+            .unwrap();
+        let rust_type =
+            |ty| -> TokenStream { convert_ast_type_to_rust_type(ty, context).parse().unwrap() };
+
+        let mut stream_bound = None;
+        let (decode, args) = match &function.param {
+            FunctionParam::None => (quote! {}, quote! {}),
+            FunctionParam::Simple(ty) => {
+                let ty = rust_type(ty);
+                (
+                    quote! {
+                        let request: #ty = glass_transport::server::dispatch::decode_request(&message)?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                    },
+                    quote! { request },
+                )
+            }
+            FunctionParam::Named(request) => {
+                let request_path: TokenStream = context
+                    .declared_path(inherited.file, &request.name)
+                    .parse()
+                    .unwrap();
+                let arg_names = request
+                    .fields
+                    .iter()
+                    .map(|arg| format_ident!("{}", arg.name));
+                (
+                    quote! {
+                        let request: #request_path = glass_transport::server::dispatch::decode_request(&message)?;
+                        glass_transport::validation::Validate::validate(&request)?;
+                    },
+                    quote! { #(request.#arg_names),* },
+                )
+            }
+            FunctionParam::Stream(ty) => {
+                let ty = rust_type(ty);
+                stream_bound = Some(quote! {
+                    <T as #trait_path>::InputStream<#ty>: From<glass_transport::server::dispatch::Requests<#ty>>
+                });
+                (
+                    quote! {
+                        let (requests, failure) = glass_transport::server::dispatch::decode_stream::<#ty>(&message, requests)?;
+                    },
+                    quote! { requests.into() },
+                )
+            }
+        };
+
+        let encode_error = match function.throws.is_empty() {
+            true => quote! { glass_transport::server::dispatch::encode_service_error },
+            false => quote! { glass_transport::server::dispatch::encode_declared_error },
+        };
+
+        let is_stream_param = matches!(function.param, FunctionParam::Stream(_));
+        let is_stream_return = matches!(function.return_type, Some(FunctionReturn::Stream(_)));
+        let respond = match (is_stream_param, is_stream_return) {
+            (false, false) => quote! {
+                glass_transport::server::dispatch::respond(&message, result, #encode_error)
+            },
+            (true, false) => quote! {
+                let response = glass_transport::server::dispatch::respond(&message, result, #encode_error)?;
+                Ok(failure.answer(glass_transport::server::dispatch::single_response(response)))
+            },
+            (false, true) => quote! {
+                Ok(glass_transport::server::dispatch::respond_stream(message, result, #encode_error))
+            },
+            (true, true) => quote! {
+                let responses = glass_transport::server::dispatch::respond_stream(message, result, #encode_error);
+                Ok(failure.answer(responses))
+            },
+        };
+
+        let arm = quote! {
+            Some(#wire_name) => {
+                #decode
+                let result = <T as #trait_path>::#function_name(&self.service, #args).await;
+                #respond
+            }
+        };
+
+        Self {
+            wire_name,
+            is_streaming: is_stream_param || is_stream_return,
+            arm,
+            stream_bound,
+        }
+    }
+}
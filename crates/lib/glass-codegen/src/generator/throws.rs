@@ -0,0 +1,111 @@
+use crate::generator::context::Context;
+use crate::generator::util::convert_ast_type_to_rust_type;
+use glass_parser::ast::interface::{Function, Interface};
+use glass_parser::ast::types::Type;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+/// Generates the error enum of every function declaring errors.
+///
+/// Each enum holds a variant per declared error, plus a `Service` variant for failures
+/// of the service itself, and maps them to their status code and payload on the wire.
+pub fn generate_function_errors(interface: &Interface, context: &Context) -> Vec<TokenStream> {
+    interface
+        .functions
+        .iter()
+        .filter(|function| !function.throws.is_empty())
+        .map(|function| generate_function_error(&interface.name, function, context))
+        .collect()
+}
+
+fn generate_function_error(interface: &str, function: &Function, context: &Context) -> TokenStream {
+    let error_name = format_ident!("{}", function.error_name(interface));
+    let doc = format!(" Errors declared by `{interface}::{}`.", function.name);
+
+    let variants = function
+        .throws
+        .iter()
+        .map(|error_ref| format_ident!("{}", error_ref.name()))
+        .collect::<Vec<_>>();
+    let types = function
+        .throws
+        .iter()
+        .map(|error_ref| {
+            let type_name =
+                convert_ast_type_to_rust_type(&Type::Schema(error_ref.clone()), context);
+            type_name.parse::<TokenStream>().unwrap()
+        })
+        .collect::<Vec<_>>();
+    let indices = (0..function.throws.len())
+        .map(|index| Literal::u8_unsuffixed(index as u8))
+        .collect::<Vec<_>>();
+    let codes = indices
+        .iter()
+        .enumerate()
+        .map(|(index, literal)| match index {
+            0 => quote! { Status::FIRST_DECLARED },
+            _ => quote! { Status::FIRST_DECLARED + #literal },
+        });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum #error_name<E> {
+            #(#variants(#types),)*
+            /// Failure of the service, not declared by the function.
+            Service(E),
+        }
+
+        #(
+            impl<E> From<#types> for #error_name<E> {
+                fn from(error: #types) -> Self {
+                    Self::#variants(error)
+                }
+            }
+        )*
+
+        impl<E> glass_transport::message::error::DeclaredError for #error_name<E>
+        where
+            E: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            fn status(&self) -> glass_transport::message::status::Status {
+                use glass_transport::message::status::Status;
+
+                match self {
+                    #(Self::#variants(_) => Status::Custom(#codes),)*
+                    Self::Service(_) => Status::Internal,
+                }
+            }
+
+            fn encode(&self) -> Result<Vec<u8>, glass_transport::message::EncodeError> {
+                match self {
+                    #(Self::#variants(error) => glass_transport::message::encode_payload(error),)*
+                    Self::Service(error) => glass_transport::message::encode_payload(error),
+                }
+            }
+
+            fn decode(
+                status: glass_transport::message::status::Status,
+                payload: &[u8],
+            ) -> Option<Result<Self, glass_transport::message::DecodeError>> {
+                use glass_transport::message::decode_payload;
+                use glass_transport::message::status::Status;
+
+                // Unlike failures of the service, the ones of the server carry no payload.
+                let index = match status {
+                    Status::Internal if payload.is_empty() => return None,
+                    Status::Internal => return Some(decode_payload(payload).map(Self::Service)),
+                    Status::Custom(code) => code.checked_sub(Status::FIRST_DECLARED)?,
+                    _ => return None,
+                };
+
+                let result = match index {
+                    #(#indices => decode_payload(payload).map(Self::#variants),)*
+                    _ => return None,
+                };
+
+                Some(result)
+            }
+        }
+    }
+}
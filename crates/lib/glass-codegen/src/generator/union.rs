@@ -0,0 +1,63 @@
+use crate::generator::annotations::{generate_attributes, generate_derives, generate_docs};
+use crate::generator::context::Context;
+use crate::generator::derives::union_derives;
+use crate::generator::util::convert_ast_field_type_to_rust_type;
+use crate::generator::validate::generate_union_validate;
+use glass_parser::ast::types::Type;
+use glass_parser::ast::union::Union;
+use glass_parser::validator::annotations::AnnotationTarget;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Generates a Rust enum for a Glass union.
+///
+/// Unions are adjacently tagged, so each value is encoded as a map holding the
+/// variant name under `type` and the schema payload under `value`. This keeps the
+/// CBOR representation self-describing for non-Rust peers.
+pub fn generate_union(union: &Union, context: &Context) -> TokenStream {
+    let union_name = format_ident!("{}", union.name);
+
+    let mut variants = Vec::new();
+    for variant in &union.variants {
+        let variant_name = format_ident!("{}", variant.name);
+        let payload = Type::Schema(variant.payload.clone());
+        let payload: TokenStream =
+            convert_ast_field_type_to_rust_type(&payload, &union.name, context)
+                .parse()
+                .unwrap();
+
+        let docs = generate_docs(&variant.docs);
+        let attributes =
+            generate_attributes(&variant.annotations, AnnotationTarget::Variant, context);
+
+        let generated = quote! {
+            #docs
+            #attributes
+            #variant_name(#payload),
+        };
+
+        variants.push(generated);
+    }
+
+    let docs = generate_docs(&union.docs);
+    let attributes = generate_attributes(&union.annotations, AnnotationTarget::Union, context);
+    let mut derives = union_derives(union, context);
+    derives.extend(["serde::Serialize", "serde::Deserialize"]);
+    let derives = generate_derives(&union.annotations, &derives);
+
+    let validate = generate_union_validate(union);
+
+    let generated = quote! {
+        #docs
+        #derives
+        #attributes
+        #[serde(tag = "type", content = "value")]
+        pub enum #union_name {
+            #(#variants)*
+        }
+
+        #validate
+    };
+
+    generated
+}
@@ -1,11 +1,51 @@
-use glass_parser::ast::types::{OptionType, PrimitiveType, Type, VectorType};
+use crate::generator::context::Context;
+use crate::generator::options::MapKind;
+use glass_parser::ast::types::{GenericType, MapType, OptionType, PrimitiveType, Type, VectorType};
 
-pub fn convert_ast_type_to_rust_type(ast_type: &Type) -> String {
+pub fn convert_ast_type_to_rust_type(ast_type: &Type, context: &Context) -> String {
     match ast_type {
         Type::Primitive(primitive) => convert_ast_primitive_to_string(primitive),
-        Type::Option(option) => convert_ast_option_to_string(option),
-        Type::Vector(vector) => convert_ast_vector_to_string(vector),
-        Type::Schema(schema_ref) => schema_ref.0.to_owned(),
+        Type::Option(option) => convert_ast_option_to_string(option, context),
+        Type::Vector(vector) => convert_ast_vector_to_string(vector, context),
+        Type::Map(map) => convert_ast_map_to_string(map, context),
+        Type::Schema(schema_ref) => context.schema_ref_path(schema_ref),
+        Type::Generic(generic) => convert_ast_generic_to_string(generic, context, |arg| {
+            convert_ast_type_to_rust_type(arg, context)
+        }),
+    }
+}
+
+/// Converts the type of a value held inline by the schema or union named `owner`.
+///
+/// References closing a cycle back to `owner` are boxed, as the generated type would
+/// otherwise be infinitely sized. Vectors and maps already live on the heap.
+pub fn convert_ast_field_type_to_rust_type(
+    ast_type: &Type,
+    owner: &str,
+    context: &Context,
+) -> String {
+    match ast_type {
+        Type::Option(option) => {
+            let inner_type = convert_ast_field_type_to_rust_type(&option.inner, owner, context);
+            format!("Option<{inner_type}>")
+        }
+        Type::Schema(schema_ref) if context.validated_file.is_recursive_ref(owner, schema_ref) => {
+            format!("Box<{}>", context.schema_ref_path(schema_ref))
+        }
+        // Type arguments may be held inline by the generic schema, so they are boxed as well.
+        Type::Generic(generic) => {
+            let generic_type = convert_ast_generic_to_string(generic, context, |arg| {
+                convert_ast_field_type_to_rust_type(arg, owner, context)
+            });
+            match context
+                .validated_file
+                .is_recursive_ref(owner, &generic.schema)
+            {
+                true => format!("Box<{generic_type}>"),
+                false => generic_type,
+            }
+        }
+        _ => convert_ast_type_to_rust_type(ast_type, context),
     }
 }
 
@@ -25,15 +65,44 @@ fn convert_ast_primitive_to_string(primitive_type: &PrimitiveType) -> String {
         PrimitiveType::F32 => "f32".to_string(),
         PrimitiveType::F64 => "f64".to_string(),
         PrimitiveType::Bool => "bool".to_string(),
+        // `ByteBuf` is a `Vec<u8>` that serializes as a byte string instead of an array.
+        PrimitiveType::Bytes => "serde_bytes::ByteBuf".to_string(),
+        PrimitiveType::Timestamp => "std::time::SystemTime".to_string(),
+        PrimitiveType::Duration => "std::time::Duration".to_string(),
+        PrimitiveType::Ulid => "ulid::Ulid".to_string(),
+        PrimitiveType::Uuid => "uuid::Uuid".to_string(),
     }
 }
 
-fn convert_ast_option_to_string(option_type: &OptionType) -> String {
-    let inner_type = convert_ast_type_to_rust_type(&option_type.inner);
+fn convert_ast_option_to_string(option_type: &OptionType, context: &Context) -> String {
+    let inner_type = convert_ast_type_to_rust_type(&option_type.inner, context);
     format!("Option<{inner_type}>")
 }
 
-fn convert_ast_vector_to_string(vector_type: &VectorType) -> String {
-    let inner_type = convert_ast_type_to_rust_type(&vector_type.inner);
+fn convert_ast_vector_to_string(vector_type: &VectorType, context: &Context) -> String {
+    let inner_type = convert_ast_type_to_rust_type(&vector_type.inner, context);
     format!("Vec<{inner_type}>")
 }
+
+fn convert_ast_generic_to_string(
+    generic_type: &GenericType,
+    context: &Context,
+    convert_arg: impl Fn(&Type) -> String,
+) -> String {
+    let args = generic_type
+        .args
+        .iter()
+        .map(convert_arg)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{}<{args}>", context.schema_ref_path(&generic_type.schema))
+}
+
+fn convert_ast_map_to_string(map_type: &MapType, context: &Context) -> String {
+    let key_type = convert_ast_type_to_rust_type(&map_type.key, context);
+    let value_type = convert_ast_type_to_rust_type(&map_type.value, context);
+    match context.options.map_kind {
+        MapKind::HashMap => format!("std::collections::HashMap<{key_type}, {value_type}>"),
+        MapKind::BTreeMap => format!("std::collections::BTreeMap<{key_type}, {value_type}>"),
+    }
+}
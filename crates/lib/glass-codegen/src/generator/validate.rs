@@ -0,0 +1,274 @@
+use crate::generator::context::Context;
+use crate::generator::defaults::generate_literal;
+use crate::generator::schema::{generate_type_param_bounds, generate_type_params};
+use glass_parser::ast::annotation::Annotation;
+use glass_parser::ast::enumeration::Enum;
+use glass_parser::ast::literal::Literal;
+use glass_parser::ast::schema::{Schema, SchemaField};
+use glass_parser::ast::types::{PrimitiveType, Type};
+use glass_parser::ast::union::Union;
+use glass_parser::validator::TypeKind;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Generates the `Validate` impl of a schema.
+///
+/// Fields are checked against their constraints, and the schemas and unions they hold
+/// are validated in turn, with violations reported under the path of the nested field.
+/// Values of a type parameter are validated as well, the parameters being bound by `Validate`.
+pub fn generate_schema_validate(schema: &Schema, context: &Context) -> TokenStream {
+    let schema_name = format_ident!("{}", schema.name);
+    let type_params = generate_type_params(schema);
+    let bounds =
+        generate_type_param_bounds(schema, quote! { glass_transport::validation::Validate });
+    let checks = schema
+        .fields
+        .iter()
+        .filter_map(|field| generate_field_checks(field, context))
+        .collect::<Vec<_>>();
+
+    let body = if checks.is_empty() {
+        quote! { Ok(()) }
+    } else {
+        quote! {
+            let mut errors = glass_transport::validation::ValidationErrors::default();
+            #(#checks)*
+            errors.into_result()
+        }
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        impl #type_params glass_transport::validation::Validate for #schema_name #type_params #bounds {
+            fn validate(&self) -> Result<(), glass_transport::validation::ValidationErrors> {
+                #body
+            }
+        }
+    }
+}
+
+/// Generates the `Validate` impl of an enum, which has nothing to check, so that it can be
+/// given as the argument of a generic schema.
+pub fn generate_enum_validate(enumeration: &Enum) -> TokenStream {
+    let enum_name = format_ident!("{}", enumeration.name);
+    quote! {
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for #enum_name {
+            fn validate(&self) -> Result<(), glass_transport::validation::ValidationErrors> {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Generates the `Validate` impl of a union, which validates the payload of the variant.
+pub fn generate_union_validate(union: &Union) -> TokenStream {
+    let union_name = format_ident!("{}", union.name);
+    let arms = union.variants.iter().map(|variant| {
+        let variant_name = format_ident!("{}", variant.name);
+        quote! { Self::#variant_name(payload) => payload.validate(), }
+    });
+
+    // A reference to an empty union is not uninhabited, so the union itself is matched.
+    let scrutinee = match union.variants.is_empty() {
+        true => quote! { *self },
+        false => quote! { self },
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        impl glass_transport::validation::Validate for #union_name {
+            fn validate(&self) -> Result<(), glass_transport::validation::ValidationErrors> {
+                match #scrutinee {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+fn generate_field_checks(field: &SchemaField, context: &Context) -> Option<TokenStream> {
+    let field_name = format_ident!("{}", field.name);
+    let path = &field.name;
+
+    let (ty, is_option) = match &field.ty {
+        Type::Option(option_type) => (option_type.inner.as_ref(), true),
+        ty => (ty, false),
+    };
+
+    let constraints = field
+        .annotations
+        .iter()
+        .filter_map(|annotation| generate_constraint(annotation, ty, path))
+        .collect::<Vec<_>>();
+    let constraints = match (constraints.is_empty(), is_option) {
+        (true, _) => None,
+        (false, true) => Some(quote! {
+            if let Some(value) = &self.#field_name {
+                #(#constraints)*
+            }
+        }),
+        (false, false) => Some(quote! {
+            let value = &self.#field_name;
+            #(#constraints)*
+        }),
+    };
+
+    let nested = generate_nested(
+        &field.ty,
+        quote! { self.#field_name },
+        quote! { #path },
+        0,
+        context,
+    );
+
+    match (constraints, nested) {
+        (None, None) => None,
+        (constraints, nested) => Some(quote! {
+            {
+                #constraints
+                #nested
+            }
+        }),
+    }
+}
+
+/// Check of a single constraint on `value`, a reference to the (non optional) field value.
+fn generate_constraint(annotation: &Annotation, ty: &Type, path: &str) -> Option<TokenStream> {
+    let argument = annotation.args.first().map(|arg| &arg.value);
+    let check = match (annotation.name.as_str(), argument) {
+        ("len", Some(range)) => {
+            let message = format!("length must be in {range}");
+            let len = match ty {
+                Type::Primitive(PrimitiveType::String) => quote! { value.chars().count() },
+                _ => quote! { value.len() },
+            };
+            let range = generate_range(range, &PrimitiveType::U64);
+            quote! {
+                if !(#range).contains(&#len) {
+                    errors.push(#path, #message);
+                }
+            }
+        }
+        ("non_empty", _) => quote! {
+            if value.is_empty() {
+                errors.push(#path, "must not be empty");
+            }
+        },
+        ("range", Some(range)) => {
+            let Type::Primitive(primitive) = ty else {
+                return None;
+            };
+            let message = format!("must be in {range}");
+            let range = generate_range(range, primitive);
+            quote! {
+                if !(#range).contains(value) {
+                    errors.push(#path, #message);
+                }
+            }
+        }
+        ("pattern", Some(Literal::String(pattern))) => {
+            let message = format!("must match `{pattern}`");
+            quote! {
+                static PATTERN: std::sync::LazyLock<glass_transport::validation::Regex> =
+                    std::sync::LazyLock::new(|| glass_transport::validation::Regex::new(#pattern).unwrap());
+                if !PATTERN.is_match(value) {
+                    errors.push(#path, #message);
+                }
+            }
+        }
+        _ => return None,
+    };
+
+    // Each check lives in its own block, so `PATTERN` statics never clash.
+    Some(quote! { { #check } })
+}
+
+fn generate_range(range: &Literal, primitive: &PrimitiveType) -> TokenStream {
+    let Literal::Range {
+        start,
+        end,
+        inclusive,
+    } = range
+    else {
+        unreachable!("constraint arguments are validated");
+    };
+
+    let start = start
+        .as_ref()
+        .map(|start| generate_literal(primitive, start));
+    let end = end.as_ref().map(|end| generate_literal(primitive, end));
+    match inclusive {
+        true => quote! { #start..=#end },
+        false => quote! { #start..#end },
+    }
+}
+
+/// Validates the schemas and unions held by a value, going through options, vectors and maps.
+///
+/// `value` is an expression of the value, which the checks borrow, and `path` an expression
+/// of the path to report violations under.
+fn generate_nested(
+    ty: &Type,
+    value: TokenStream,
+    path: TokenStream,
+    depth: usize,
+    context: &Context,
+) -> Option<TokenStream> {
+    let item = format_ident!("item_{depth}");
+    let item_path = format_ident!("path_{depth}");
+
+    match ty {
+        Type::Primitive(_) => None,
+        // Type parameters are not declared, and are validated through their bound.
+        Type::Schema(schema_ref) => match context.validated_file.resolve(schema_ref) {
+            Ok(resolved) if resolved.kind == TypeKind::Enum => None,
+            _ => Some(quote! { errors.nest(#path, #value.validate()); }),
+        },
+        Type::Generic(_) => Some(quote! { errors.nest(#path, #value.validate()); }),
+        Type::Option(option_type) => {
+            let nested = generate_nested(
+                &option_type.inner,
+                quote! { #item },
+                path,
+                depth + 1,
+                context,
+            )?;
+            Some(quote! {
+                if let Some(#item) = &#value {
+                    #nested
+                }
+            })
+        }
+        Type::Vector(vector_type) => {
+            let nested = generate_nested(
+                &vector_type.inner,
+                quote! { #item },
+                quote! { &#item_path },
+                depth + 1,
+                context,
+            )?;
+            Some(quote! {
+                for (index, #item) in #value.iter().enumerate() {
+                    let #item_path = format!("{}[{index}]", #path);
+                    #nested
+                }
+            })
+        }
+        Type::Map(map_type) => {
+            let nested = generate_nested(
+                &map_type.value,
+                quote! { #item },
+                quote! { &#item_path },
+                depth + 1,
+                context,
+            )?;
+            Some(quote! {
+                for (key, #item) in #value.iter() {
+                    let #item_path = format!("{}[{key:?}]", #path);
+                    #nested
+                }
+            })
+        }
+    }
+}
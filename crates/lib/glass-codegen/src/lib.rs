@@ -1,2 +1,8 @@
+//! Generates Rust code from validated Glass files.
+//!
+//! Generated code depends on `glass-transport`, `serde` and `async-trait`, along with
+//! `serde_bytes`, `ulid` and `uuid` for files using the `bytes`, `ulid` and `uuid` types.
+//! Streams are named through `glass_transport::Stream`, so no other crate is needed.
+
 pub mod generator;
 pub mod prelude;
@@ -1,2 +1,4 @@
-pub use crate::generator::generate;
+pub use crate::generator::{
+    FieldKeys, GeneratorOptions, MapKind, Plugin, generate, generate_with_options,
+};
 pub use glass_parser::prelude::*;
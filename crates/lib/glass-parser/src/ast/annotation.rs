@@ -0,0 +1,91 @@
+use crate::ast::literal::Literal;
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::{Pair, Pairs};
+
+/// Annotation attached to a declaration
+///
+/// Written as `@name` or `@name(args)` right before the annotated item, e.g.
+/// `@deprecated("use v2")` or `@rust(derive = "Default")`. The grammar accepts any
+/// name, it is up to the validator and the generators to make sense of them.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub name: String,
+    pub args: Vec<AnnotationArg>,
+    pub span: Span,
+}
+
+impl Annotation {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+
+        let name = inner
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?
+            .as_str()
+            .to_owned();
+
+        let args = match inner.next() {
+            Some(args) => args
+                .into_inner()
+                .map(AnnotationArg::try_parse)
+                .collect::<ParserResult<_>>()?,
+            None => vec![],
+        };
+
+        Ok(Self { name, args, span })
+    }
+
+    /// Parses the annotations leading a declaration, leaving the rest of its pairs untouched.
+    pub fn parse_leading(pairs: &mut Pairs<'_, Rule>) -> ParserResult<Vec<Self>> {
+        let mut annotations = Vec::new();
+        while let Some(pair) = pairs.peek()
+            && pair.as_rule() == Rule::annotation
+        {
+            pairs.next();
+            annotations.push(Self::try_parse(pair)?);
+        }
+
+        Ok(annotations)
+    }
+
+    /// Finds the first argument with the given name.
+    pub fn named_arg(&self, name: &str) -> Option<&AnnotationArg> {
+        self.args
+            .iter()
+            .find(|arg| arg.name.as_deref() == Some(name))
+    }
+}
+
+/// Annotation argument
+///
+/// Arguments are either positional, e.g. `@timeout(5s)`,
+/// or named, e.g. `@rust(derive = "Default")`.
+#[derive(Debug, Clone)]
+pub struct AnnotationArg {
+    pub name: Option<String>,
+    pub value: Literal,
+    pub span: Span,
+}
+
+impl AnnotationArg {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut name = None;
+        let mut value = None;
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                Rule::snake_case_ident => name = Some(pair.as_str().to_owned()),
+                _ => value = Some(Literal::try_parse(pair)?),
+            }
+        }
+
+        Ok(Self {
+            name,
+            value: value.ok_or(ParserError::NoNextToken(span))?,
+            span,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::doc;
+use crate::ast::literal::Literal;
+use crate::ast::span::Span;
+use crate::ast::types::{PrimitiveType, Type};
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+
+/// Constant definition
+///
+/// Composed of its name, a primitive type and the literal value it holds,
+/// e.g. `const MAX_PAGE_SIZE: u32 = 500;`.
+#[derive(Debug, Clone)]
+pub struct Constant {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub ty: PrimitiveType,
+    pub value: Literal,
+    pub span: Span,
+    pub name_span: Span,
+    pub value_span: Span,
+}
+
+impl Constant {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
+
+        let (name, name_span) = match inner.next() {
+            Some(pair) => (pair.as_str().to_owned(), Span::from(pair.as_span())),
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        // The grammar only accepts primitive types here.
+        let ty = match inner.next().map(Type::try_parse).transpose()? {
+            Some(Type::Primitive(primitive)) => primitive,
+            Some(_) => return Err(ParserError::UnexpectedRule(Rule::const_decl)),
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        let value_pair = inner.next().ok_or(ParserError::NoNextToken(span))?;
+        let value_span = Span::from(value_pair.as_span());
+        let value = Literal::try_parse(value_pair)?;
+
+        Ok(Self {
+            docs,
+            annotations,
+            name,
+            ty,
+            value,
+            span,
+            name_span,
+            value_span,
+        })
+    }
+}
@@ -0,0 +1,18 @@
+use crate::parser::Rule;
+use pest::iterators::Pairs;
+
+/// Parses the `///` doc comments leading a declaration, leaving the rest of its pairs untouched.
+///
+/// Lines are kept verbatim after the slashes, leading space included, the same way
+/// `rustc` turns doc comments into `#[doc]` attributes.
+pub fn parse_leading(pairs: &mut Pairs<'_, Rule>) -> Vec<String> {
+    let mut docs = Vec::new();
+    while let Some(pair) = pairs.peek()
+        && pair.as_rule() == Rule::doc_comment
+    {
+        pairs.next();
+        docs.push(pair.as_str()["///".len()..].trim_end().to_owned());
+    }
+
+    docs
+}
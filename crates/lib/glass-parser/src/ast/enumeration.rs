@@ -0,0 +1,88 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::doc;
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+
+/// Enum definition
+///
+/// Composed of its name and a vector of unit variants.
+#[derive(Debug, Clone)]
+pub struct Enum {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+    pub span: Span,
+    pub name_span: Span,
+}
+
+impl Enum {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
+
+        let (enum_name, name_span) = match inner.next() {
+            Some(pair) => (pair.as_str().to_owned(), Span::from(pair.as_span())),
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        let enum_body_pair = match inner.next() {
+            Some(pair) => pair,
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        let variants = enum_body_pair
+            .into_inner()
+            .map(EnumVariant::try_parse)
+            .collect::<ParserResult<_>>()?;
+
+        Ok(Self {
+            docs,
+            annotations,
+            name: enum_name,
+            variants,
+            span,
+            name_span,
+        })
+    }
+}
+
+/// Enum variant definition
+///
+/// Composed only of its name, as variants carry no payload.
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub span: Span,
+}
+
+impl EnumVariant {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
+        let name = inner
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?
+            .as_str()
+            .to_owned();
+
+        Ok(Self {
+            docs,
+            annotations,
+            name,
+            span,
+        })
+    }
+}
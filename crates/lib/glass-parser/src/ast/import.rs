@@ -0,0 +1,32 @@
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+
+/// Import statement
+///
+/// Holds the path to another Glass file, relative to the
+/// file that declared the import.
+#[derive(Debug, Clone)]
+pub struct Import {
+    pub path: String,
+    pub span: Span,
+}
+
+impl Import {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let literal = pair
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?;
+        let path = literal
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?
+            .as_str()
+            .to_owned();
+
+        Ok(Self { path, span })
+    }
+}
@@ -1,3 +1,7 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::doc;
+use crate::ast::schema::{Schema, SchemaField, SchemaRef};
+use crate::ast::span::Span;
 use crate::ast::types::Type;
 use crate::error::ParserError;
 use crate::parser::Rule;
@@ -7,22 +11,51 @@ use pest::iterators::Pair;
 /// Function parameter
 ///
 /// Can be either Stream or Simple depending on how
-/// it was declared.
+/// it was declared, Named for a list of named arguments or None
+/// when the function takes nothing, sent as an empty payload.
+///
+/// Named arguments are carried on the wire by an implicit request schema, whose
+/// fields are the arguments. It is also added to the schemas of the file, so it is
+/// validated and generated like any other schema.
 #[derive(Debug, Clone)]
 pub enum FunctionParam {
     Stream(Type),
     Simple(Type),
+    Named(Schema),
+    None,
 }
 
 impl FunctionParam {
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
-        let inner_pair = pair.into_inner().next().ok_or(ParserError::NoNextToken)?;
+        let span = Span::from(pair.as_span());
+        let Some(inner_pair) = pair.into_inner().next() else {
+            return Ok(FunctionParam::None);
+        };
+
+        if inner_pair.as_rule() == Rule::function_args {
+            let fields = inner_pair
+                .into_inner()
+                .map(Self::parse_arg)
+                .collect::<ParserResult<_>>()?;
+
+            // The request is named once the function and its interface are known.
+            return Ok(FunctionParam::Named(Schema {
+                docs: vec![],
+                annotations: vec![],
+                name: String::new(),
+                type_params: vec![],
+                fields,
+                reserved: vec![],
+                span,
+                name_span: span,
+            }));
+        }
 
         let type_pair = inner_pair
             .clone()
             .into_inner()
             .next()
-            .ok_or(ParserError::NoNextToken)?;
+            .ok_or(ParserError::NoNextToken(span))?;
         let ty = Type::try_parse(type_pair)?;
 
         match inner_pair.as_rule() {
@@ -31,6 +64,39 @@ impl FunctionParam {
             _ => Err(ParserError::UnexpectedRule(inner_pair.as_rule())),
         }
     }
+
+    fn parse_arg(pair: Pair<'_, Rule>) -> ParserResult<SchemaField> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let name = inner
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?
+            .as_str()
+            .to_owned();
+        let ty = Type::try_parse(inner.next().ok_or(ParserError::NoNextToken(span))?)?;
+        let annotations = inner
+            .map(Annotation::try_parse)
+            .collect::<ParserResult<_>>()?;
+
+        Ok(SchemaField {
+            docs: vec![],
+            annotations,
+            name,
+            ty,
+            default: None,
+            number: None,
+            span,
+        })
+    }
+
+    /// Type sent on the wire, which is the request schema for named arguments.
+    pub fn ty(&self) -> Option<Type> {
+        match self {
+            FunctionParam::Stream(ty) | FunctionParam::Simple(ty) => Some(ty.clone()),
+            FunctionParam::Named(request) => Some(Type::Schema(SchemaRef::new(&request.name))),
+            FunctionParam::None => None,
+        }
+    }
 }
 
 /// Function return
@@ -45,13 +111,17 @@ pub enum FunctionReturn {
 
 impl FunctionReturn {
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
-        let inner_pair = pair.into_inner().next().ok_or(ParserError::NoNextToken)?;
+        let span = Span::from(pair.as_span());
+        let inner_pair = pair
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?;
 
         let type_pair = inner_pair
             .clone()
             .into_inner()
             .next()
-            .ok_or(ParserError::NoNextToken)?;
+            .ok_or(ParserError::NoNextToken(span))?;
         let ty = Type::try_parse(type_pair)?;
 
         match inner_pair.as_rule() {
@@ -64,64 +134,147 @@ impl FunctionReturn {
 
 /// Function definition
 ///
-/// Composed of the function name, its input parameter and
-/// its return type, which might be optional.
+/// Composed of the function name, its input parameter,
+/// its return type, which might be optional, and the schemas
+/// of the errors it declares.
 #[derive(Debug, Clone)]
 pub struct Function {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
     pub name: String,
     pub param: FunctionParam,
     pub return_type: Option<FunctionReturn>,
+    pub throws: Vec<SchemaRef>,
+    pub span: Span,
 }
 
 impl Function {
+    /// Name of the enum gathering the declared errors, e.g. `AccountsWithdrawError`
+    /// for `withdraw` of `Accounts`.
+    pub fn error_name(&self, interface: &str) -> String {
+        format!("{interface}{}Error", to_pascal_case(&self.name))
+    }
+
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
         let mut inner_pair = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner_pair);
+        let annotations = Annotation::parse_leading(&mut inner_pair)?;
 
         let name = inner_pair
             .next()
-            .ok_or(ParserError::NoNextToken)?
+            .ok_or(ParserError::NoNextToken(span))?
             .as_str()
             .to_string();
 
-        let param = FunctionParam::try_parse(inner_pair.next().ok_or(ParserError::NoNextToken)?)?;
+        let param =
+            FunctionParam::try_parse(inner_pair.next().ok_or(ParserError::NoNextToken(span))?)?;
 
-        let return_type = inner_pair
-            .next()
-            .map(FunctionReturn::try_parse)
-            .transpose()?;
+        let mut return_type = None;
+        let mut throws = Vec::new();
+        for pair in inner_pair {
+            match pair.as_rule() {
+                Rule::function_return => return_type = Some(FunctionReturn::try_parse(pair)?),
+                Rule::function_throws => {
+                    throws = pair
+                        .into_inner()
+                        .map(SchemaRef::try_parse)
+                        .collect::<ParserResult<_>>()?;
+                }
+                rule => return Err(ParserError::UnexpectedRule(rule)),
+            }
+        }
 
         Ok(Self {
+            docs,
+            annotations,
             name,
             param,
             return_type,
+            throws,
+            span,
         })
     }
 }
 
 /// Interface definition
 ///
-/// Composed of its name and a vector of functions.
+/// Composed of its name, the interfaces it extends and a vector of functions.
 #[derive(Debug, Clone)]
 pub struct Interface {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
     pub name: String,
+    pub extends: Vec<SchemaRef>,
     pub functions: Vec<Function>,
+    pub span: Span,
+    pub name_span: Span,
 }
 
 impl Interface {
+    /// Implicit request schemas of the functions taking named arguments.
+    pub fn request_schemas(&self) -> impl Iterator<Item = &Schema> {
+        self.functions
+            .iter()
+            .filter_map(|function| match &function.param {
+                FunctionParam::Named(request) => Some(request),
+                _ => None,
+            })
+    }
+
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
         let mut inner_pair = pair.into_inner();
-        let name = inner_pair
-            .next()
-            .ok_or(ParserError::NoNextToken)?
-            .as_str()
-            .to_owned();
-        let body = inner_pair.next().ok_or(ParserError::NoNextToken)?;
+        let docs = doc::parse_leading(&mut inner_pair);
+        let annotations = Annotation::parse_leading(&mut inner_pair)?;
+        let name_pair = inner_pair.next().ok_or(ParserError::NoNextToken(span))?;
+        let name = name_pair.as_str().to_owned();
+        let name_span = Span::from(name_pair.as_span());
+        let mut body = inner_pair.next().ok_or(ParserError::NoNextToken(span))?;
+
+        let mut extends = Vec::new();
+        if body.as_rule() == Rule::interface_extends {
+            extends = body
+                .into_inner()
+                .map(SchemaRef::try_parse)
+                .collect::<ParserResult<_>>()?;
+            body = inner_pair.next().ok_or(ParserError::NoNextToken(span))?;
+        }
 
-        let functions = body
+        let mut functions = body
             .into_inner()
             .map(Function::try_parse)
-            .collect::<ParserResult<_>>()?;
+            .collect::<ParserResult<Vec<_>>>()?;
 
-        Ok(Self { name, functions })
+        // Requests are prefixed by the interface, so functions sharing a name across
+        // interfaces don't clash, e.g. `transfer` of `Accounts` takes `AccountsTransferRequest`.
+        for function in &mut functions {
+            if let FunctionParam::Named(request) = &mut function.param {
+                request.name = format!("{name}{}Request", to_pascal_case(&function.name));
+                request.docs = vec![format!(" Arguments of `{name}::{}`.", function.name)];
+            }
+        }
+
+        Ok(Self {
+            docs,
+            annotations,
+            name,
+            extends,
+            functions,
+            span,
+            name_span,
+        })
     }
 }
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
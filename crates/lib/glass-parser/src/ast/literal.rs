@@ -0,0 +1,150 @@
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Literal value for Glass
+///
+/// Durations are written as an integer followed by a `ms`, `s`, `m` or `h` unit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    String(String),
+    Integer(i128),
+    /// Integer above `i128::MAX`, which only fits in a `u128`.
+    LargeInteger(u128),
+    Float(f64),
+    Bool(bool),
+    Duration(Duration),
+    /// Range of numbers, e.g. `1..64` or `0.0..=1.0`, whose bounds are integers or floats.
+    Range {
+        start: Option<Box<Literal>>,
+        end: Option<Box<Literal>>,
+        inclusive: bool,
+    },
+}
+
+impl Literal {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let pair = match pair.as_rule() {
+            Rule::literal => pair
+                .into_inner()
+                .next()
+                .ok_or(ParserError::NoNextToken(span))?,
+            _ => pair,
+        };
+
+        let value = pair.as_str();
+        match pair.as_rule() {
+            Rule::string_literal => {
+                let content = pair
+                    .into_inner()
+                    .next()
+                    .ok_or(ParserError::NoNextToken(span))?;
+                Ok(Literal::String(content.as_str().to_owned()))
+            }
+            Rule::bool_literal => Ok(Literal::Bool(value == "true")),
+            Rule::integer_literal => value
+                .parse()
+                .map(Literal::Integer)
+                .or_else(|_| value.parse().map(Literal::LargeInteger))
+                .map_err(|_| ParserError::InvalidLiteral(span)),
+            Rule::float_literal => value
+                .parse()
+                .map(Literal::Float)
+                .map_err(|_| ParserError::InvalidLiteral(span)),
+            Rule::duration_literal => {
+                let unit_start = value
+                    .find(|char: char| !char.is_ascii_digit())
+                    .ok_or(ParserError::InvalidLiteral(span))?;
+                let (amount, unit) = value.split_at(unit_start);
+                let amount: u64 = amount
+                    .parse()
+                    .map_err(|_| ParserError::InvalidLiteral(span))?;
+
+                let duration = match unit {
+                    "ms" => Some(Duration::from_millis(amount)),
+                    "s" => Some(Duration::from_secs(amount)),
+                    "m" => amount.checked_mul(60).map(Duration::from_secs),
+                    "h" => amount.checked_mul(3600).map(Duration::from_secs),
+                    // The grammar itself guarantees this is unreachable, so we can safely assume it so.
+                    _ => unreachable!(),
+                };
+
+                duration
+                    .map(Literal::Duration)
+                    .ok_or(ParserError::InvalidLiteral(span))
+            }
+            Rule::range_literal => {
+                let mut start = None;
+                let mut end = None;
+                let mut operator = None;
+                for pair in pair.into_inner() {
+                    match pair.as_rule() {
+                        Rule::range_operator => operator = Some(pair.as_str() == "..="),
+                        Rule::range_bound => {
+                            let bound = pair
+                                .into_inner()
+                                .next()
+                                .ok_or(ParserError::NoNextToken(span))?;
+                            let bound = Some(Box::new(Literal::try_parse(bound)?));
+                            match operator {
+                                None => start = bound,
+                                Some(_) => end = bound,
+                            }
+                        }
+                        rule => return Err(ParserError::UnexpectedRule(rule)),
+                    }
+                }
+
+                Ok(Literal::Range {
+                    start,
+                    end,
+                    inclusive: operator.ok_or(ParserError::NoNextToken(span))?,
+                })
+            }
+            rule => Err(ParserError::UnexpectedRule(rule)),
+        }
+    }
+
+    /// Name of the kind of literal, as shown in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Literal::String(_) => "string",
+            Literal::Integer(_) | Literal::LargeInteger(_) => "integer",
+            Literal::Float(_) => "float",
+            Literal::Bool(_) => "bool",
+            Literal::Duration(_) => "duration",
+            Literal::Range { .. } => "range",
+        }
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::String(value) => write!(f, "{value:?}"),
+            Literal::Integer(value) => write!(f, "{value}"),
+            Literal::LargeInteger(value) => write!(f, "{value}"),
+            Literal::Float(value) => write!(f, "{value:?}"),
+            Literal::Bool(value) => write!(f, "{value}"),
+            Literal::Duration(value) => write!(f, "{}ms", value.as_millis()),
+            Literal::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                write!(f, "{}", if *inclusive { "..=" } else { ".." })?;
+                if let Some(end) = end {
+                    write!(f, "{end}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
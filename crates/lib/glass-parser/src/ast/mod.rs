@@ -1,14 +1,28 @@
+use crate::ast::constant::Constant;
+use crate::ast::enumeration::Enum;
+use crate::ast::import::Import;
 use crate::ast::interface::Interface;
+use crate::ast::package::Package;
 use crate::ast::schema::Schema;
+use crate::ast::union::Union;
 use crate::parser::{Parser as GlassParser, Rule};
 use crate::prelude::*;
 use pest::Parser;
 use std::path::PathBuf;
 use tracing::{error, info};
 
+pub mod annotation;
+pub mod constant;
+pub mod doc;
+pub mod enumeration;
+pub mod import;
 pub mod interface;
+pub mod literal;
+pub mod package;
 pub mod schema;
+pub mod span;
 pub mod types;
+pub mod union;
 
 /// Defines a Glass file
 ///
@@ -17,8 +31,13 @@ pub mod types;
 #[derive(Debug, Clone)]
 pub struct File {
     pub path: PathBuf,
+    pub package: Option<Package>,
+    pub imports: Vec<Import>,
+    pub constants: Vec<Constant>,
     pub interfaces: Vec<Interface>,
     pub schemas: Vec<Schema>,
+    pub enums: Vec<Enum>,
+    pub unions: Vec<Union>,
 }
 
 impl File {
@@ -30,8 +49,13 @@ impl File {
 
         Ok(Self {
             path,
+            package: None,
+            imports: vec![],
+            constants: vec![],
             interfaces: vec![],
             schemas: vec![],
+            enums: vec![],
+            unions: vec![],
         })
     }
 
@@ -51,8 +75,13 @@ impl File {
             }
         };
 
+        let mut package = None;
+        let mut imports = vec![];
+        let mut constants = vec![];
         let mut interfaces = vec![];
         let mut schemas = vec![];
+        let mut enums = vec![];
+        let mut unions = vec![];
 
         for pair in pairs {
             match pair.as_rule() {
@@ -60,14 +89,34 @@ impl File {
                     let inner = pair.into_inner();
                     for pair in inner {
                         match pair.as_rule() {
+                            Rule::package_decl => {
+                                package = Some(Package::try_parse(pair)?);
+                            }
+                            Rule::import_decl => {
+                                let import = Import::try_parse(pair)?;
+                                imports.push(import);
+                            }
+                            Rule::const_decl => {
+                                let constant = Constant::try_parse(pair)?;
+                                constants.push(constant);
+                            }
                             Rule::interface_decl => {
                                 let interface = Interface::try_parse(pair)?;
+                                schemas.extend(interface.request_schemas().cloned());
                                 interfaces.push(interface);
                             }
                             Rule::schema_decl => {
                                 let schema = Schema::try_parse(pair)?;
                                 schemas.push(schema);
                             }
+                            Rule::enum_decl => {
+                                let enumeration = Enum::try_parse(pair)?;
+                                enums.push(enumeration);
+                            }
+                            Rule::union_decl => {
+                                let union = Union::try_parse(pair)?;
+                                unions.push(union);
+                            }
                             Rule::EOI => (),
                             _ => {
                                 error!(path = ?self.path, "Unexpected rule: {:?}", pair.as_rule());
@@ -84,12 +133,43 @@ impl File {
             }
         }
 
+        self.package = package;
+        self.imports = imports;
+        self.constants = constants;
         self.interfaces = interfaces;
         self.schemas = schemas;
+        self.enums = enums;
+        self.unions = unions;
 
         Ok(())
     }
 
+    /// Name of the Rust module the file is generated as, derived from its file name.
+    ///
+    /// Characters not allowed in identifiers become underscores, and names clashing with
+    /// a Rust keyword are suffixed with one, e.g. `user-types.glass` is `user_types` and
+    /// `mod.glass` is `mod_`.
+    pub fn module_name(&self) -> String {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let mut name = stem
+            .chars()
+            .map(|char| match char.is_ascii_alphanumeric() {
+                true => char,
+                false => '_',
+            })
+            .collect::<String>();
+        if !name.starts_with(|char: char| char.is_ascii_alphabetic() || char == '_') {
+            name.insert(0, '_');
+        }
+
+        escape_keyword(name)
+    }
+
     #[tracing::instrument(skip(self))]
     fn read_file_contents(&self) -> ParserResult<String> {
         // If we are calling this, then we already validated that the file at least exists.
@@ -101,9 +181,29 @@ impl File {
     }
 }
 
+/// Suffixes a module name with `_` when it is `_` or a Rust keyword, e.g. `async_`.
+pub(crate) fn escape_keyword(mut name: String) -> String {
+    if name == "_" || RUST_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+
+    name
+}
+
+/// Keywords of every Rust edition, strict and reserved, none of which names a module.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
 #[cfg(test)]
 mod tests {
     use crate::ast::interface::{FunctionParam, FunctionReturn};
+    use crate::ast::literal::Literal;
+    use crate::ast::schema::{DefaultValue, Reserved};
     use crate::ast::types::{PrimitiveType, Type};
     use crate::prelude::*;
     use std::fs::File as StdFile;
@@ -131,6 +231,21 @@ mod tests {
         assert!(matches!(result, Err(ParserError::FileNotFound(_))));
     }
 
+    #[test]
+    fn test_file_module_name() {
+        // Module names only depend on the path, so the file is never read.
+        let module_name = |path: &str| {
+            let mut file = File::try_new(PathBuf::from("Cargo.toml")).unwrap();
+            file.path = PathBuf::from(path);
+            file.module_name()
+        };
+        assert_eq!(module_name("users.glass"), "users");
+        assert_eq!(module_name("dir/User-Types.glass"), "user_types");
+        assert_eq!(module_name("2fa.glass"), "_2fa");
+        assert_eq!(module_name("mod.glass"), "mod_");
+        assert_eq!(module_name("type.glass"), "type_");
+    }
+
     #[test]
     fn test_parse_empty_file() {
         let (path, cleanup) = create_temp_file("empty", "");
@@ -140,6 +255,7 @@ mod tests {
         assert!(result.is_ok());
         assert!(file.schemas.is_empty());
         assert!(file.interfaces.is_empty());
+        assert!(file.enums.is_empty());
 
         cleanup();
     }
@@ -206,4 +322,534 @@ mod tests {
 
         cleanup();
     }
+
+    #[test]
+    fn test_parse_enum() {
+        let content = r#"
+            enum Status {
+                Active;
+                Suspended;
+            }
+
+            schema Account {
+                status: Status;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("enum_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        let result = file.try_parse();
+
+        assert!(result.is_ok());
+        assert_eq!(file.enums.len(), 1);
+
+        let enumeration = &file.enums[0];
+        assert_eq!(enumeration.name, "Status");
+        assert_eq!(enumeration.variants.len(), 2);
+        assert_eq!(enumeration.variants[0].name, "Active");
+        assert_eq!(enumeration.variants[1].name, "Suspended");
+
+        let schema = &file.schemas[0];
+        assert!(matches!(schema.fields[0].ty, Type::Schema(_)));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_union() {
+        let content = r#"
+            schema UserCreated { id: u64; }
+            schema UserDeleted { id: u64; }
+
+            union Event {
+                Created(UserCreated);
+                Deleted(UserDeleted);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("union_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        let result = file.try_parse();
+
+        assert!(result.is_ok());
+        assert_eq!(file.unions.len(), 1);
+
+        let union = &file.unions[0];
+        assert_eq!(union.name, "Event");
+        assert_eq!(union.variants.len(), 2);
+        assert_eq!(union.variants[0].name, "Created");
+        assert_eq!(union.variants[0].payload.path, "UserCreated");
+        assert_eq!(union.variants[1].name, "Deleted");
+        assert_eq!(union.variants[1].payload.path, "UserDeleted");
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_map_type() {
+        let content = r#"
+            schema Labels {
+                values: map<string, vec<u32>>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("map_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let Type::Map(map_type) = &file.schemas[0].fields[0].ty else {
+            panic!("Expected a map type");
+        };
+        assert_eq!(*map_type.key, Type::Primitive(PrimitiveType::String));
+        assert!(matches!(*map_type.value, Type::Vector(_)));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_well_known_types() {
+        let content = r#"
+            schema Upload {
+                id: ulid;
+                owner: uuid;
+                content: bytes;
+                created_at: timestamp;
+                ttl: option<duration>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("well_known_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let fields = &file.schemas[0].fields;
+        assert_eq!(fields[0].ty, Type::Primitive(PrimitiveType::Ulid));
+        assert_eq!(fields[1].ty, Type::Primitive(PrimitiveType::Uuid));
+        assert_eq!(fields[2].ty, Type::Primitive(PrimitiveType::Bytes));
+        assert_eq!(fields[3].ty, Type::Primitive(PrimitiveType::Timestamp));
+        let Type::Option(ttl) = &fields[4].ty else {
+            panic!("expected an option, got {:?}", fields[4].ty);
+        };
+        assert_eq!(*ttl.inner, Type::Primitive(PrimitiveType::Duration));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_imports() {
+        let content = r#"
+            import "users.glass";
+            import "common/pagination.glass";
+
+            interface Billing {
+                fn charge(User);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("import_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        assert_eq!(file.imports.len(), 2);
+        assert_eq!(file.imports[0].path, "users.glass");
+        assert_eq!(file.imports[1].path, "common/pagination.glass");
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_package() {
+        let content = r#"
+            package acme.billing.v1;
+
+            import "users.glass";
+
+            schema Invoice {
+                user: acme.users.v1.User;
+                total: u64;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("package_parse", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let package = file.package.as_ref().unwrap();
+        assert_eq!(package.segments, vec!["acme", "billing", "v1"]);
+        assert_eq!(package.qualify("Invoice"), "acme.billing.v1.Invoice");
+
+        let Type::Schema(schema_ref) = &file.schemas[0].fields[0].ty else {
+            panic!("Expected a schema reference");
+        };
+        assert_eq!(schema_ref.package(), Some("acme.users.v1"));
+        assert_eq!(schema_ref.name(), "User");
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_field_numbers() {
+        let content = r#"
+            schema User {
+                reserved 3, 4;
+                reserved "email";
+
+                id: u64 = 1;
+                name: string = 2;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("field_numbers", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let schema = &file.schemas[0];
+        let numbers = schema
+            .fields
+            .iter()
+            .map(|field| field.number)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![Some(1), Some(2)]);
+
+        assert_eq!(schema.reserved.len(), 3);
+        assert!(matches!(
+            schema.reserved[1],
+            Reserved::Number { number: 4, .. }
+        ));
+        assert!(matches!(&schema.reserved[2], Reserved::Name { name, .. } if name == "email"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_field_number_out_of_range() {
+        let content = "schema User { id: u64 = 4294967296; }";
+        let (path, cleanup) = create_temp_file("field_number_range", content);
+        let mut file = File::try_new(path).unwrap();
+
+        let result = file.try_parse();
+        assert!(matches!(result, Err(ParserError::InvalidFieldNumber(_))));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let content = r#"
+            @deprecated("use Account instead")
+            @rust(derive = "Default")
+            schema User {
+                @sensitive
+                password: string;
+            }
+
+            interface Users {
+                @idempotent
+                @timeout(30s)
+                fn get(User) -> User;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("annotations", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let schema = &file.schemas[0];
+        assert_eq!(schema.annotations.len(), 2);
+        assert_eq!(schema.annotations[0].name, "deprecated");
+        assert!(matches!(
+            &schema.annotations[0].args[0].value,
+            Literal::String(note) if note == "use Account instead"
+        ));
+        assert!(matches!(
+            schema.annotations[1].named_arg("derive").map(|arg| &arg.value),
+            Some(Literal::String(derive)) if derive == "Default"
+        ));
+        assert_eq!(schema.fields[0].annotations[0].name, "sensitive");
+        assert!(schema.fields[0].annotations[0].args.is_empty());
+
+        let function = &file.interfaces[0].functions[0];
+        assert_eq!(function.annotations.len(), 2);
+        assert!(matches!(
+            function.annotations[1].args[0].value,
+            Literal::Duration(duration) if duration.as_secs() == 30
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let content = r#"
+            // Plain comments are dropped.
+            /* So are
+               block comments. */
+            /// A registered user.
+            ///
+            /// Docs span several lines.
+            schema User {
+                //// Four slashes make a plain comment.
+                /// Unique identifier.
+                id: u64; // Trailing comment.
+            }
+
+            /// User management.
+            interface Users {
+                /// Finds a user.
+                fn get(User) -> /* inline */ User;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("comments", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let schema = &file.schemas[0];
+        assert_eq!(
+            schema.docs,
+            vec![" A registered user.", "", " Docs span several lines."]
+        );
+        assert_eq!(schema.fields[0].docs, vec![" Unique identifier."]);
+
+        let interface = &file.interfaces[0];
+        assert_eq!(interface.docs, vec![" User management."]);
+        assert_eq!(interface.functions[0].docs, vec![" Finds a user."]);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_dangling_doc_comments() {
+        let content = r#"
+            /// Documents nothing.
+            package acme.store;
+            /// Neither does this one.
+            import "users.glass";
+
+            schema User {
+                /// Unique identifier.
+                id: u64;
+                /// Dropped before reserved numbers.
+                reserved 2;
+                /// Still documents the field.
+                reserved: bool;
+                /// Ends the body.
+            }
+
+            enum Status {
+                Active;
+                /// Ends the body.
+            }
+
+            /// Ends the file.
+        "#;
+        let (path, cleanup) = create_temp_file("dangling_docs", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let schema = &file.schemas[0];
+        assert!(schema.docs.is_empty());
+        assert_eq!(schema.fields.len(), 2);
+        assert_eq!(schema.fields[0].docs, vec![" Unique identifier."]);
+        assert_eq!(schema.fields[1].docs, vec![" Still documents the field."]);
+        assert_eq!(file.enums[0].variants.len(), 1);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_field_defaults() {
+        let content = r#"
+            schema Config {
+                retries: u32 default 3 = 1;
+                nickname: option<string> default none = 2;
+                role: Role default Admin = 3;
+                timeout: duration default 30s = 4;
+                id: u64 = 5;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("field_defaults", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let defaults = file.schemas[0]
+            .fields
+            .iter()
+            .map(|field| field.default.as_ref().map(|default| default.value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            defaults,
+            vec![
+                Some(DefaultValue::Literal(Literal::Integer(3))),
+                Some(DefaultValue::None),
+                Some(DefaultValue::Variant("Admin".to_owned())),
+                Some(DefaultValue::Literal(Literal::Duration(
+                    std::time::Duration::from_secs(30)
+                ))),
+                None,
+            ]
+        );
+        assert_eq!(file.schemas[0].fields[0].number, Some(1));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_constants() {
+        let content = r#"
+            /// Largest page a client may ask for.
+            const MAX_PAGE_SIZE: u32 = 500;
+            const API_VERSION: string = "v2";
+            const DEFAULT_TIMEOUT: duration = 30s;
+
+            schema User { id: u64; }
+        "#;
+        let (path, cleanup) = create_temp_file("constants", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        assert_eq!(file.constants.len(), 3);
+        assert_eq!(file.constants[0].name, "MAX_PAGE_SIZE");
+        assert_eq!(file.constants[0].ty, PrimitiveType::U32);
+        assert_eq!(file.constants[0].value, Literal::Integer(500));
+        assert_eq!(
+            file.constants[0].docs,
+            vec![" Largest page a client may ask for."]
+        );
+        assert_eq!(file.constants[1].value, Literal::String("v2".to_owned()));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_function_params() {
+        let content = r#"
+            schema AccountId { value: u64; }
+
+            interface Accounts {
+                fn transfer(from: AccountId, to: AccountId, amount: u64 @range(1..),) -> AccountId;
+                fn get(AccountId) -> AccountId;
+                fn ping();
+                fn close(AccountId) throws Locked | bank.Frozen;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("named_params", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let transfer = &file.interfaces[0].functions[0];
+        let FunctionParam::Named(request) = &transfer.param else {
+            panic!("expected named arguments, found {:?}", transfer.param);
+        };
+        assert_eq!(request.name, "AccountsTransferRequest");
+        let args = request
+            .fields
+            .iter()
+            .map(|arg| arg.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(args, vec!["from", "to", "amount"]);
+        assert_eq!(request.fields[2].annotations[0].name, "range");
+        assert!(matches!(
+            file.interfaces[0].functions[1].param,
+            FunctionParam::Simple(_)
+        ));
+        assert!(matches!(
+            file.interfaces[0].functions[2].param,
+            FunctionParam::None
+        ));
+        let throws = &file.interfaces[0].functions[3].throws;
+        assert_eq!(throws[0].path, "Locked");
+        assert_eq!(throws[1].path, "bank.Frozen");
+        assert!(file.interfaces[0].functions[0].throws.is_empty());
+
+        // The request is declared along with the schemas of the file.
+        assert_eq!(file.schemas.len(), 2);
+        assert_eq!(file.schemas[1].name, "AccountsTransferRequest");
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_interface_extends() {
+        let content = r#"
+            interface Health { fn ping(); }
+            interface Billing extends Health, common.Admin {
+                fn charge(u64);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("interface_extends", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        assert!(file.interfaces[0].extends.is_empty());
+        let extends = &file.interfaces[1].extends;
+        assert_eq!(extends[0].path, "Health");
+        assert_eq!(extends[1].path, "common.Admin");
+        assert_eq!(file.interfaces[1].functions.len(), 1);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_generic_schemas() {
+        let content = r#"
+            schema Pair<A, B> {
+                first: A;
+                second: B;
+            }
+
+            schema Listing {
+                users: Pair<u32, vec<common.User>>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("generic_schemas", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        assert_eq!(file.schemas[0].type_params, ["A", "B"]);
+        assert!(file.schemas[1].type_params.is_empty());
+
+        let Type::Generic(generic) = &file.schemas[1].fields[0].ty else {
+            panic!("expected a generic type");
+        };
+        assert_eq!(generic.schema.path, "Pair");
+        assert!(matches!(
+            generic.args.as_slice(),
+            [Type::Primitive(PrimitiveType::U32), Type::Vector(vector)]
+                if matches!(vector.inner.as_ref(), Type::Schema(user) if user.path == "common.User")
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_field_constraints() {
+        let content = r#"
+            schema User {
+                name: string @len(1..64);
+                age: u8 @range(..=150) = 2;
+                score: f64 @range(-1.5..);
+                tags: vec<string> @non_empty default none;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("field_constraints", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let fields = &file.schemas[0].fields;
+        assert_eq!(fields[0].annotations[0].name, "len");
+        assert_eq!(
+            fields[0].annotations[0].args[0].value,
+            Literal::Range {
+                start: Some(Box::new(Literal::Integer(1))),
+                end: Some(Box::new(Literal::Integer(64))),
+                inclusive: false,
+            }
+        );
+        assert_eq!(
+            fields[1].annotations[0].args[0].value,
+            Literal::Range {
+                start: None,
+                end: Some(Box::new(Literal::Integer(150))),
+                inclusive: true,
+            }
+        );
+        assert_eq!(fields[1].number, Some(2));
+        assert_eq!(fields[2].annotations[0].args[0].value.to_string(), "-1.5..");
+        assert_eq!(fields[3].annotations[0].name, "non_empty");
+        assert!(fields[3].default.is_some());
+
+        cleanup();
+    }
 }
@@ -0,0 +1,52 @@
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+use std::fmt::{Display, Formatter};
+
+/// Package declaration
+///
+/// Namespaces every type and interface declared in a file,
+/// e.g. `package acme.billing.v1;`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Package {
+    pub segments: Vec<String>,
+    pub span: Span,
+}
+
+impl Package {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let package_ident = pair
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?;
+        let segments = package_ident
+            .as_str()
+            .split('.')
+            .map(ToOwned::to_owned)
+            .collect();
+
+        Ok(Self { segments, span })
+    }
+
+    /// Names of the modules nesting the generated declarations, one per segment, suffixed
+    /// like the modules of files when they are Rust keywords, e.g. `acme::async_::v1`.
+    pub fn module_names(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .map(|segment| crate::ast::escape_keyword(segment.clone()))
+            .collect()
+    }
+
+    /// Prefixes a name with this package, e.g. `acme.billing.v1.Invoice`.
+    pub fn qualify(&self, name: &str) -> String {
+        format!("{self}.{name}")
+    }
+}
+
+impl Display for Package {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.segments.join("."))
+    }
+}
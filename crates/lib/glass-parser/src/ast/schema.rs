@@ -1,83 +1,302 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::doc;
+use crate::ast::literal::Literal;
+use crate::ast::span::Span;
 use crate::ast::types::Type;
 use crate::parser::Rule;
 use crate::prelude::*;
 use pest::iterators::Pair;
+use std::fmt::{Display, Formatter};
 
 /// Schema definition
 ///
-/// Composed of its name and a vector of fields.
+/// Composed of its name, a vector of fields and the field numbers and names
+/// it reserved.
+///
+/// Generic schemas, e.g. `schema Page<T> { ... }`, declare type parameters that
+/// their fields may refer to as any other named type.
 #[derive(Debug, Clone)]
 pub struct Schema {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
     pub name: String,
+    pub type_params: Vec<String>,
     pub fields: Vec<SchemaField>,
+    pub reserved: Vec<Reserved>,
+    pub span: Span,
+    pub name_span: Span,
 }
 
 impl Schema {
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
         let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
 
-        let schema_name = match inner.next() {
-            Some(pair) => pair.as_str().to_owned(),
+        let (schema_name, name_span) = match inner.next() {
+            Some(pair) => (pair.as_str().to_owned(), Span::from(pair.as_span())),
             None => {
-                return Err(ParserError::NoNextToken);
+                return Err(ParserError::NoNextToken(span));
             }
         };
 
-        let schema_body_pair = match inner.next() {
+        let mut schema_body_pair = match inner.next() {
             Some(pair) => pair,
             None => {
-                return Err(ParserError::NoNextToken);
+                return Err(ParserError::NoNextToken(span));
             }
         };
 
+        let mut type_params = Vec::new();
+        if schema_body_pair.as_rule() == Rule::type_params {
+            type_params = schema_body_pair
+                .into_inner()
+                .map(|pair| pair.as_str().to_owned())
+                .collect();
+            schema_body_pair = inner.next().ok_or(ParserError::NoNextToken(span))?;
+        }
+
         let mut schema_fields = Vec::new();
+        let mut reserved = Vec::new();
         schema_body_pair.into_inner().try_for_each(|pair| {
-            let schema_field = SchemaField::try_parse(pair)?;
-            schema_fields.push(schema_field);
+            match pair.as_rule() {
+                Rule::schema_field => schema_fields.push(SchemaField::try_parse(pair)?),
+                Rule::reserved_decl => {
+                    for item in pair.into_inner() {
+                        reserved.push(Reserved::try_parse(item)?);
+                    }
+                }
+                rule => return Err(ParserError::UnexpectedRule(rule)),
+            }
             Ok::<(), ParserError>(())
         })?;
 
         Ok(Self {
+            docs,
+            annotations,
             name: schema_name,
+            type_params,
             fields: schema_fields,
+            reserved,
+            span,
+            name_span,
         })
     }
+
+    /// Whether the schema declares type parameters.
+    pub fn is_generic(&self) -> bool {
+        !self.type_params.is_empty()
+    }
 }
 
 /// Schema field definition
 ///
-/// Composed of its name and type.
+/// Composed of its name, type, optional default value and optional field number.
+/// Once fields are keyed by [FieldKeys::Numbers], numbers identify the field on the
+/// wire regardless of its name, so it can be safely renamed.
 #[derive(Debug, Clone)]
 pub struct SchemaField {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
     pub name: String,
     pub ty: Type,
+    pub default: Option<FieldDefault>,
+    pub number: Option<u32>,
+    pub span: Span,
 }
 
 impl SchemaField {
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
         let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let mut annotations = Annotation::parse_leading(&mut inner)?;
         let field_name = match inner.next() {
             Some(pair) => pair.as_str().to_owned(),
             None => {
-                return Err(ParserError::NoNextToken);
+                return Err(ParserError::NoNextToken(span));
             }
         };
 
         let field_type = match inner.next() {
             Some(pair) => Type::try_parse(pair)?,
             None => {
-                return Err(ParserError::NoNextToken);
+                return Err(ParserError::NoNextToken(span));
             }
         };
 
+        // Constraints such as `@len(1..64)` may also follow the type.
+        let mut default = None;
+        let mut number = None;
+        for pair in inner {
+            match pair.as_rule() {
+                Rule::annotation => annotations.push(Annotation::try_parse(pair)?),
+                Rule::field_default => default = Some(FieldDefault::try_parse(pair)?),
+                Rule::field_number => number = Some(parse_field_number(pair)?),
+                rule => return Err(ParserError::UnexpectedRule(rule)),
+            }
+        }
+
         Ok(Self {
+            docs,
+            annotations,
             name: field_name,
             ty: field_type,
+            default,
+            number,
+            span,
         })
     }
 }
 
+/// How schema fields are keyed once encoded.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum FieldKeys {
+    /// Fields are keyed by their name.
+    #[default]
+    Names,
+    /// Fields of numbered schemas are keyed by their number, which is smaller
+    /// on the wire and survives renames. Other schemas keep using names.
+    Numbers,
+}
+
+/// Value a field takes when it is missing, e.g. `retries: u32 default 3;`
+#[derive(Debug, Clone)]
+pub struct FieldDefault {
+    pub value: DefaultValue,
+    pub span: Span,
+}
+
+impl FieldDefault {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let value = pair
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?;
+
+        let value = match value.as_rule() {
+            Rule::none_literal => DefaultValue::None,
+            Rule::variant_ident => DefaultValue::Variant(value.as_str().to_owned()),
+            _ => DefaultValue::Literal(Literal::try_parse(value)?),
+        };
+
+        Ok(Self { value, span })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+    /// `none`, only valid for options.
+    None,
+    Literal(Literal),
+    /// Variant of the enum the field holds.
+    Variant(String),
+}
+
+impl Display for DefaultValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultValue::None => write!(f, "none"),
+            DefaultValue::Literal(literal) => write!(f, "{literal}"),
+            DefaultValue::Variant(variant) => write!(f, "{variant}"),
+        }
+    }
+}
+
+/// Field number or name a schema no longer uses, and that must never be reused.
+#[derive(Debug, Clone)]
+pub enum Reserved {
+    Number { number: u32, span: Span },
+    Name { name: String, span: Span },
+}
+
+impl Reserved {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let item = pair
+            .into_inner()
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?;
+
+        match item.as_rule() {
+            Rule::field_number => Ok(Self::Number {
+                number: parse_field_number(item)?,
+                span,
+            }),
+            Rule::string_literal => {
+                let name = item
+                    .into_inner()
+                    .next()
+                    .ok_or(ParserError::NoNextToken(span))?
+                    .as_str()
+                    .to_owned();
+
+                Ok(Self::Name { name, span })
+            }
+            rule => Err(ParserError::UnexpectedRule(rule)),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Reserved::Number { span, .. } | Reserved::Name { span, .. } => *span,
+        }
+    }
+}
+
+fn parse_field_number(pair: Pair<'_, Rule>) -> ParserResult<u32> {
+    pair.as_str()
+        .parse()
+        .map_err(|_| ParserError::InvalidFieldNumber(Span::from(pair.as_span())))
+}
+
 /// SchemaRef is a way for the [Type] to refer back to a [Schema] without
 /// causing a circular dependency between the types.
+///
+/// The grammar cannot tell named types apart, so it may also point to an
+/// [Enum](crate::ast::enumeration::Enum) or a [Union](crate::ast::union::Union);
+/// the validator resolves which one it is. Interfaces extended by another one are
+/// referenced the same way.
+///
+/// References to types of another package hold their fully qualified name.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SchemaRef(pub String);
+pub struct SchemaRef {
+    pub path: String,
+    pub span: Span,
+}
+
+impl SchemaRef {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        Ok(Self {
+            path: pair.as_str().to_owned(),
+            span: Span::from(pair.as_span()),
+        })
+    }
+
+    /// Name of the referenced type, without its package.
+    pub fn name(&self) -> &str {
+        self.path
+            .rsplit_once('.')
+            .map_or(self.path.as_str(), |(_, name)| name)
+    }
+
+    /// Package the reference is qualified with, if any.
+    pub fn package(&self) -> Option<&str> {
+        self.path.rsplit_once('.').map(|(package, _)| package)
+    }
+}
+
+impl std::fmt::Display for SchemaRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
@@ -1,10 +1,14 @@
 use crate::ast::schema::SchemaRef;
+use crate::ast::span::Span;
 use crate::error::ParserError;
 use crate::parser::Rule;
 use crate::prelude::ParserResult;
 use pest::iterators::Pair;
 
 /// Primitive types for Glass
+///
+/// Besides scalars, this includes well-known types that have a fixed CBOR
+/// representation on the wire, documented on each variant.
 #[derive(Debug, Clone, PartialEq)]
 pub enum PrimitiveType {
     String,
@@ -21,6 +25,44 @@ pub enum PrimitiveType {
     F32,
     F64,
     Bool,
+    /// Encoded as a CBOR byte string.
+    Bytes,
+    /// Encoded as a map of `secs_since_epoch` and `nanos_since_epoch` unsigned integers.
+    Timestamp,
+    /// Encoded as a map of `secs` and `nanos` unsigned integers.
+    Duration,
+    /// Encoded as its 26 characters Crockford base32 text string.
+    Ulid,
+    /// Encoded as a 16 bytes CBOR byte string.
+    Uuid,
+}
+
+impl std::fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keyword = match self {
+            PrimitiveType::String => "string",
+            PrimitiveType::U8 => "u8",
+            PrimitiveType::U16 => "u16",
+            PrimitiveType::U32 => "u32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::U128 => "u128",
+            PrimitiveType::I8 => "i8",
+            PrimitiveType::I16 => "i16",
+            PrimitiveType::I32 => "i32",
+            PrimitiveType::I64 => "i64",
+            PrimitiveType::I128 => "i128",
+            PrimitiveType::F32 => "f32",
+            PrimitiveType::F64 => "f64",
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::Bytes => "bytes",
+            PrimitiveType::Timestamp => "timestamp",
+            PrimitiveType::Duration => "duration",
+            PrimitiveType::Ulid => "ulid",
+            PrimitiveType::Uuid => "uuid",
+        };
+
+        write!(f, "{keyword}")
+    }
 }
 
 /// Option type for Glass
@@ -30,6 +72,7 @@ pub enum PrimitiveType {
 #[derive(Debug, Clone, PartialEq)]
 pub struct OptionType {
     pub inner: Box<Type>,
+    pub span: Span,
 }
 
 /// Vector type for Glass
@@ -39,6 +82,26 @@ pub struct OptionType {
 #[derive(Debug, Clone, PartialEq)]
 pub struct VectorType {
     pub inner: Box<Type>,
+    pub span: Span,
+}
+
+/// Map type for Glass
+///
+/// Both fields are a [Box] so to avoid problems
+/// with recursive types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapType {
+    pub key: Box<Type>,
+    pub value: Box<Type>,
+    pub span: Span,
+}
+
+/// Generic schema instantiated with type arguments, e.g. `Page<User>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericType {
+    pub schema: SchemaRef,
+    pub args: Vec<Type>,
+    pub span: Span,
 }
 
 /// Main type definition for Glass
@@ -47,16 +110,19 @@ pub enum Type {
     Primitive(PrimitiveType),
     Option(OptionType),
     Vector(VectorType),
+    Map(MapType),
     Schema(SchemaRef),
+    Generic(GenericType),
 }
 
 impl Type {
     pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
         match pair.as_rule() {
             Rule::type_decl => {
                 let inner_type = match pair.into_inner().next() {
                     Some(inner_type) => inner_type,
-                    None => return Err(ParserError::NoNextToken),
+                    None => return Err(ParserError::NoNextToken(span)),
                 };
 
                 Self::try_parse(inner_type)
@@ -68,28 +134,69 @@ impl Type {
             Rule::option_type => {
                 let inner_type = match pair.into_inner().next() {
                     Some(inner_type) => inner_type,
-                    None => return Err(ParserError::NoNextToken),
+                    None => return Err(ParserError::NoNextToken(span)),
                 };
 
                 Ok(Type::Option(OptionType {
                     inner: Box::new(Self::try_parse(inner_type)?),
+                    span,
                 }))
             }
             Rule::vector_type => {
                 let inner_type = match pair.into_inner().next() {
                     Some(inner_type) => inner_type,
-                    None => return Err(ParserError::NoNextToken),
+                    None => return Err(ParserError::NoNextToken(span)),
                 };
 
                 Ok(Type::Vector(VectorType {
                     inner: Box::new(Self::try_parse(inner_type)?),
+                    span,
+                }))
+            }
+            Rule::map_type => {
+                let mut inner = pair.into_inner();
+                let key_type = inner.next().ok_or(ParserError::NoNextToken(span))?;
+                let value_type = inner.next().ok_or(ParserError::NoNextToken(span))?;
+
+                Ok(Type::Map(MapType {
+                    key: Box::new(Self::try_parse(key_type)?),
+                    value: Box::new(Self::try_parse(value_type)?),
+                    span,
                 }))
             }
-            Rule::schema_ident => Ok(Type::Schema(SchemaRef(pair.as_str().to_owned()))),
+            Rule::generic_type => {
+                let mut inner = pair.into_inner();
+                let schema = inner.next().ok_or(ParserError::NoNextToken(span))?;
+                let args = inner.next().ok_or(ParserError::NoNextToken(span))?;
+
+                Ok(Type::Generic(GenericType {
+                    schema: SchemaRef::try_parse(schema)?,
+                    args: args
+                        .into_inner()
+                        .map(Self::try_parse)
+                        .collect::<ParserResult<_>>()?,
+                    span,
+                }))
+            }
+            Rule::type_ref => Ok(Type::Schema(SchemaRef::try_parse(pair)?)),
             _ => Err(ParserError::UnexpectedRule(pair.as_rule())),
         }
     }
 
+    /// Span of the type declaration.
+    ///
+    /// Primitives are not tracked, as they never need to be resolved.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Type::Primitive(_) => None,
+            Type::Option(option) => Some(option.span),
+            Type::Vector(vector) => Some(vector.span),
+            Type::Map(map) => Some(map.span),
+            Type::Schema(schema_ref) => Some(schema_ref.span),
+            Type::Generic(generic) => Some(generic.span),
+        }
+    }
+
     fn parse_string_to_primitive_type(primitive: &str) -> PrimitiveType {
         match primitive {
             "string" => PrimitiveType::String,
@@ -106,6 +213,11 @@ impl Type {
             "i128" => PrimitiveType::I128,
             "f32" => PrimitiveType::F32,
             "f64" => PrimitiveType::F64,
+            "bytes" => PrimitiveType::Bytes,
+            "timestamp" => PrimitiveType::Timestamp,
+            "duration" => PrimitiveType::Duration,
+            "ulid" => PrimitiveType::Ulid,
+            "uuid" => PrimitiveType::Uuid,
             // The grammar itself guarantees this is unreachable, so we can safely assume it so.
             _ => unreachable!(),
         }
@@ -0,0 +1,95 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::doc;
+use crate::ast::schema::SchemaRef;
+use crate::ast::span::Span;
+use crate::parser::Rule;
+use crate::prelude::*;
+use pest::iterators::Pair;
+
+/// Union definition
+///
+/// Composed of its name and a vector of variants, each one
+/// carrying a schema as its payload.
+#[derive(Debug, Clone)]
+pub struct Union {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub variants: Vec<UnionVariant>,
+    pub span: Span,
+    pub name_span: Span,
+}
+
+impl Union {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
+
+        let (union_name, name_span) = match inner.next() {
+            Some(pair) => (pair.as_str().to_owned(), Span::from(pair.as_span())),
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        let union_body_pair = match inner.next() {
+            Some(pair) => pair,
+            None => {
+                return Err(ParserError::NoNextToken(span));
+            }
+        };
+
+        let variants = union_body_pair
+            .into_inner()
+            .map(UnionVariant::try_parse)
+            .collect::<ParserResult<_>>()?;
+
+        Ok(Self {
+            docs,
+            annotations,
+            name: union_name,
+            variants,
+            span,
+            name_span,
+        })
+    }
+}
+
+/// Union variant definition
+///
+/// Composed of its name and a reference to the schema it carries.
+#[derive(Debug, Clone)]
+pub struct UnionVariant {
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub payload: SchemaRef,
+    pub span: Span,
+}
+
+impl UnionVariant {
+    pub fn try_parse(pair: Pair<'_, Rule>) -> ParserResult<Self> {
+        let span = Span::from(pair.as_span());
+        let mut inner = pair.into_inner();
+        let docs = doc::parse_leading(&mut inner);
+        let annotations = Annotation::parse_leading(&mut inner)?;
+
+        let name = inner
+            .next()
+            .ok_or(ParserError::NoNextToken(span))?
+            .as_str()
+            .to_owned();
+
+        let payload = SchemaRef::try_parse(inner.next().ok_or(ParserError::NoNextToken(span))?)?;
+
+        Ok(Self {
+            docs,
+            annotations,
+            name,
+            payload,
+            span,
+        })
+    }
+}
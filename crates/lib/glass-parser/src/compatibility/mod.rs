@@ -0,0 +1,1122 @@
+use crate::ast::constant::Constant;
+use crate::ast::enumeration::Enum;
+use crate::ast::interface::{Function, FunctionParam, FunctionReturn, Interface};
+use crate::ast::schema::{DefaultValue, FieldKeys, Schema, SchemaField, SchemaRef};
+use crate::ast::span::Span;
+use crate::ast::types::Type;
+use crate::ast::union::Union;
+use crate::prelude::*;
+use crate::validator::inheritance::InheritedFunction;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::PathBuf;
+
+/// How a change affects existing peers and code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// Existing peers and code keep working.
+    Safe,
+    /// Payloads stay compatible, but code using the generated types may no longer compile.
+    SourceBreaking,
+    /// Existing peers can no longer exchange messages with updated ones.
+    WireBreaking,
+}
+
+/// A difference between two versions of a set of Glass files.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub compatibility: Compatibility,
+    pub message: String,
+    /// File the change is found in, which is the old one for removals.
+    pub path: PathBuf,
+    pub span: Span,
+}
+
+impl Change {
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility != Compatibility::Safe
+    }
+}
+
+/// Lists the changes made to the declarations of `old` in `new`.
+///
+/// Declarations are matched by their fully qualified name, so moving one to another
+/// file of the same package is not a change. Fields are matched by what keys them on
+/// the wire, their number when `field_keys` is [FieldKeys::Numbers] and both versions of
+/// a schema are numbered, and their name otherwise.
+pub fn check_compatibility(old: &Project, new: &Project, field_keys: FieldKeys) -> Vec<Change> {
+    let old = Declarations::collect(old);
+    let new = Declarations::collect(new);
+    let mut checker = Checker {
+        field_keys,
+        ..Default::default()
+    };
+
+    checker.check_all(
+        &old.constants,
+        &new.constants,
+        "Constant",
+        Compatibility::SourceBreaking,
+        Checker::check_constant,
+    );
+    checker.check_all(
+        &old.schemas,
+        &new.schemas,
+        "Schema",
+        Compatibility::SourceBreaking,
+        Checker::check_schema,
+    );
+    checker.check_all(
+        &old.enums,
+        &new.enums,
+        "Enum",
+        Compatibility::SourceBreaking,
+        Checker::check_enum,
+    );
+    checker.check_all(
+        &old.unions,
+        &new.unions,
+        "Union",
+        Compatibility::SourceBreaking,
+        Checker::check_union,
+    );
+    checker.check_all(
+        &old.interfaces,
+        &new.interfaces,
+        "Interface",
+        Compatibility::WireBreaking,
+        Checker::check_interface,
+    );
+
+    checker.changes
+}
+
+/// A declaration along with the file declaring it.
+type Declared<'a, T> = (&'a ValidatedFile, &'a T);
+
+/// Every declaration of a project, by fully qualified name.
+#[derive(Default)]
+struct Declarations<'a> {
+    constants: BTreeMap<String, Declared<'a, Constant>>,
+    schemas: BTreeMap<String, Declared<'a, Schema>>,
+    enums: BTreeMap<String, Declared<'a, Enum>>,
+    unions: BTreeMap<String, Declared<'a, Union>>,
+    interfaces: BTreeMap<String, Declared<'a, Interface>>,
+}
+
+impl<'a> Declarations<'a> {
+    fn collect(project: &'a Project) -> Self {
+        let mut declarations = Self::default();
+        for file in &project.files {
+            for constant in file.constant_map.values() {
+                declarations
+                    .constants
+                    .insert(qualify(file, &constant.name), (file, constant));
+            }
+            for schema in file.schema_map.values() {
+                declarations
+                    .schemas
+                    .insert(qualify(file, &schema.name), (file, schema));
+            }
+            for enumeration in file.enum_map.values() {
+                declarations
+                    .enums
+                    .insert(qualify(file, &enumeration.name), (file, enumeration));
+            }
+            for union in file.union_map.values() {
+                declarations
+                    .unions
+                    .insert(qualify(file, &union.name), (file, union));
+            }
+            for interface in file.interface_map.values() {
+                declarations
+                    .interfaces
+                    .insert(qualify(file, &interface.name), (file, interface));
+            }
+        }
+
+        declarations
+    }
+}
+
+/// Spans of the declarations compared by [Checker::check_all].
+trait Declaration {
+    fn name_span(&self) -> Span;
+}
+
+impl Declaration for Constant {
+    fn name_span(&self) -> Span {
+        self.name_span
+    }
+}
+
+impl Declaration for Schema {
+    fn name_span(&self) -> Span {
+        self.name_span
+    }
+}
+
+impl Declaration for Enum {
+    fn name_span(&self) -> Span {
+        self.name_span
+    }
+}
+
+impl Declaration for Union {
+    fn name_span(&self) -> Span {
+        self.name_span
+    }
+}
+
+impl Declaration for Interface {
+    fn name_span(&self) -> Span {
+        self.name_span
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    changes: Vec<Change>,
+    field_keys: FieldKeys,
+}
+
+impl Checker {
+    fn push(
+        &mut self,
+        compatibility: Compatibility,
+        message: String,
+        file: &ValidatedFile,
+        span: Span,
+    ) {
+        self.changes.push(Change {
+            compatibility,
+            message,
+            path: file.file.path.clone(),
+            span,
+        });
+    }
+
+    /// Reports added and removed declarations, and compares the ones found in both versions.
+    fn check_all<T: Declaration>(
+        &mut self,
+        old: &BTreeMap<String, Declared<'_, T>>,
+        new: &BTreeMap<String, Declared<'_, T>>,
+        kind: &str,
+        removal: Compatibility,
+        check: fn(&mut Self, &str, Declared<'_, T>, Declared<'_, T>),
+    ) {
+        for (name, &(old_file, old_declaration)) in old {
+            match new.get(name) {
+                Some(&new_declared) => check(self, name, (old_file, old_declaration), new_declared),
+                None => self.push(
+                    removal,
+                    format!("{kind} `{name}` was removed"),
+                    old_file,
+                    old_declaration.name_span(),
+                ),
+            }
+        }
+
+        for (name, &(new_file, new_declaration)) in new {
+            if !old.contains_key(name) {
+                self.push(
+                    Compatibility::Safe,
+                    format!("{kind} `{name}` was added"),
+                    new_file,
+                    new_declaration.name_span(),
+                );
+            }
+        }
+    }
+
+    /// Constants never go over the wire, so they can only break the code using them.
+    fn check_constant(
+        &mut self,
+        name: &str,
+        old: Declared<'_, Constant>,
+        new: Declared<'_, Constant>,
+    ) {
+        let (_, old_constant) = old;
+        let (new_file, new_constant) = new;
+
+        if old_constant.ty != new_constant.ty {
+            self.push(
+                Compatibility::SourceBreaking,
+                format!(
+                    "Constant `{name}` changed type from `{}` to `{}`",
+                    old_constant.ty, new_constant.ty
+                ),
+                new_file,
+                new_constant.name_span,
+            );
+        } else if old_constant.value != new_constant.value {
+            self.push(
+                Compatibility::Safe,
+                format!(
+                    "Constant `{name}` changed value from `{}` to `{}`",
+                    old_constant.value, new_constant.value
+                ),
+                new_file,
+                new_constant.value_span,
+            );
+        }
+    }
+
+    fn check_schema(&mut self, name: &str, old: Declared<'_, Schema>, new: Declared<'_, Schema>) {
+        let (old_file, old_schema) = old;
+        let (new_file, new_schema) = new;
+
+        let is_numbered =
+            |schema: &Schema| schema.fields.iter().any(|field| field.number.is_some());
+        // Types using the schema must then be given other arguments.
+        if old_schema.type_params != new_schema.type_params {
+            let params = |schema: &Schema| match schema.is_generic() {
+                true => format!("`<{}>`", schema.type_params.join(", ")),
+                false => "none".to_owned(),
+            };
+            self.push(
+                Compatibility::SourceBreaking,
+                format!(
+                    "Schema `{name}` changed type parameters from {} to {}",
+                    params(old_schema),
+                    params(new_schema)
+                ),
+                new_file,
+                new_schema.name_span,
+            );
+        }
+
+        // Numbers only key fields on the wire when the code is generated to use them.
+        let by_numbers = self.field_keys == FieldKeys::Numbers;
+        let by_number = by_numbers && is_numbered(old_schema) && is_numbered(new_schema);
+        if is_numbered(old_schema) != is_numbered(new_schema) {
+            let compatibility = match by_numbers {
+                true => Compatibility::WireBreaking,
+                false => Compatibility::Safe,
+            };
+            self.push(
+                compatibility,
+                format!("Schema `{name}` changed whether its fields are numbered"),
+                new_file,
+                new_schema.name_span,
+            );
+        }
+
+        let find = |old_field: &SchemaField| {
+            new_schema.fields.iter().position(|new_field| {
+                if by_number {
+                    new_field.number == old_field.number
+                } else {
+                    new_field.name == old_field.name
+                }
+            })
+        };
+
+        let mut matched = HashSet::new();
+        for old_field in &old_schema.fields {
+            let Some(index) = find(old_field) else {
+                // Decoders fill missing optional fields with `None`, or with their default.
+                let compatibility = if can_be_missing(old_field) {
+                    Compatibility::SourceBreaking
+                } else {
+                    Compatibility::WireBreaking
+                };
+                self.push(
+                    compatibility,
+                    format!("Field `{name}.{}` was removed", old_field.name),
+                    old_file,
+                    old_field.span,
+                );
+                continue;
+            };
+
+            matched.insert(index);
+            let new_field = &new_schema.fields[index];
+            if new_field.name != old_field.name {
+                self.push(
+                    Compatibility::SourceBreaking,
+                    format!(
+                        "Field `{name}.{}` was renamed to `{}`",
+                        old_field.name, new_field.name
+                    ),
+                    new_file,
+                    new_field.span,
+                );
+            }
+
+            let old_type = type_signature(&old_field.ty, old_file);
+            let new_type = type_signature(&new_field.ty, new_file);
+            if old_type != new_type {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!(
+                        "Field `{name}.{}` changed type from `{old_type}` to `{new_type}`",
+                        new_field.name
+                    ),
+                    new_file,
+                    new_field.span,
+                );
+            }
+
+            let old_default = old_field.default.as_ref().map(|default| &default.value);
+            let new_default = new_field.default.as_ref().map(|default| &default.value);
+            if old_default != new_default {
+                let describe = |default: Option<&DefaultValue>| match default {
+                    Some(value) => format!("`{value}`"),
+                    None => "nothing".to_owned(),
+                };
+                self.push(
+                    Compatibility::Safe,
+                    format!(
+                        "Field `{name}.{}` changed its default from {} to {}",
+                        new_field.name,
+                        describe(old_default),
+                        describe(new_default)
+                    ),
+                    new_file,
+                    new_field.span,
+                );
+            }
+        }
+
+        for (index, new_field) in new_schema.fields.iter().enumerate() {
+            if matched.contains(&index) {
+                continue;
+            }
+
+            // Older peers never send the new field, which decoders only accept if it can be missing.
+            let compatibility = if can_be_missing(new_field) {
+                Compatibility::Safe
+            } else {
+                Compatibility::WireBreaking
+            };
+            self.push(
+                compatibility,
+                format!("Field `{name}.{}` was added", new_field.name),
+                new_file,
+                new_field.span,
+            );
+        }
+    }
+
+    fn check_enum(&mut self, name: &str, old: Declared<'_, Enum>, new: Declared<'_, Enum>) {
+        let (old_file, old_enum) = old;
+        let (new_file, new_enum) = new;
+
+        for old_variant in &old_enum.variants {
+            if !new_enum
+                .variants
+                .iter()
+                .any(|new_variant| new_variant.name == old_variant.name)
+            {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!("Variant `{name}::{}` was removed", old_variant.name),
+                    old_file,
+                    old_variant.span,
+                );
+            }
+        }
+
+        // Generated enums reject unknown variants, so older peers cannot decode new ones.
+        for new_variant in &new_enum.variants {
+            if !old_enum
+                .variants
+                .iter()
+                .any(|old_variant| old_variant.name == new_variant.name)
+            {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!("Variant `{name}::{}` was added", new_variant.name),
+                    new_file,
+                    new_variant.span,
+                );
+            }
+        }
+    }
+
+    fn check_union(&mut self, name: &str, old: Declared<'_, Union>, new: Declared<'_, Union>) {
+        let (old_file, old_union) = old;
+        let (new_file, new_union) = new;
+
+        for old_variant in &old_union.variants {
+            let Some(new_variant) = new_union
+                .variants
+                .iter()
+                .find(|new_variant| new_variant.name == old_variant.name)
+            else {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!("Variant `{name}::{}` was removed", old_variant.name),
+                    old_file,
+                    old_variant.span,
+                );
+                continue;
+            };
+
+            let old_payload = ref_signature(&old_variant.payload, old_file);
+            let new_payload = ref_signature(&new_variant.payload, new_file);
+            if old_payload != new_payload {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!(
+                        "Variant `{name}::{}` changed payload from `{old_payload}` to `{new_payload}`",
+                        new_variant.name
+                    ),
+                    new_file,
+                    new_variant.span,
+                );
+            }
+        }
+
+        for new_variant in &new_union.variants {
+            if !old_union
+                .variants
+                .iter()
+                .any(|old_variant| old_variant.name == new_variant.name)
+            {
+                self.push(
+                    Compatibility::WireBreaking,
+                    format!("Variant `{name}::{}` was added", new_variant.name),
+                    new_file,
+                    new_variant.span,
+                );
+            }
+        }
+    }
+
+    fn check_interface(
+        &mut self,
+        name: &str,
+        old: Declared<'_, Interface>,
+        new: Declared<'_, Interface>,
+    ) {
+        let (old_file, old_interface) = old;
+        let (new_file, new_interface) = new;
+
+        // Implementations must follow the parents of the generated trait.
+        let old_parents = parents_signature(old_interface, old_file);
+        let new_parents = parents_signature(new_interface, new_file);
+        for parent in new_parents.difference(&old_parents) {
+            self.push(
+                Compatibility::SourceBreaking,
+                format!("Interface `{name}` now extends `{parent}`"),
+                new_file,
+                new_interface.name_span,
+            );
+        }
+        for parent in old_parents.difference(&new_parents) {
+            self.push(
+                Compatibility::SourceBreaking,
+                format!("Interface `{name}` no longer extends `{parent}`"),
+                new_file,
+                new_interface.name_span,
+            );
+        }
+
+        // Inherited functions are served by the interface as well, so they are compared too.
+        let old_functions = old_file.interface_functions(old_interface);
+        let new_functions = new_file.interface_functions(new_interface);
+
+        let find = |function: &Function, functions: &[InheritedFunction<'_>]| {
+            functions
+                .iter()
+                .position(|other| other.function.name == function.name)
+        };
+
+        let added = new_functions
+            .iter()
+            .filter(|inherited| find(inherited.function, &old_functions).is_none())
+            .collect::<Vec<_>>();
+        let mut renamed = HashSet::new();
+
+        for old_inherited in &old_functions {
+            let old_function = old_inherited.function;
+            if let Some(index) = find(old_function, &new_functions) {
+                self.check_function(
+                    name,
+                    old_function,
+                    old_inherited.file,
+                    new_functions[index].function,
+                    new_functions[index].file,
+                );
+                continue;
+            }
+
+            // A removed function with the same signature as an added one was most likely renamed.
+            let old_signature = function_signature(old_function, old_inherited.file);
+            let rename = added.iter().find(|new_inherited| {
+                !renamed.contains(&new_inherited.function.name)
+                    && function_signature(new_inherited.function, new_inherited.file)
+                        == old_signature
+            });
+
+            match rename {
+                Some(new_inherited) => {
+                    renamed.insert(new_inherited.function.name.clone());
+                    self.push(
+                        Compatibility::WireBreaking,
+                        format!(
+                            "Function `{name}.{}` was renamed to `{}`",
+                            old_function.name, new_inherited.function.name
+                        ),
+                        new_inherited.file,
+                        new_inherited.function.span,
+                    );
+                }
+                None => self.push(
+                    Compatibility::WireBreaking,
+                    format!("Function `{name}.{}` was removed", old_function.name),
+                    old_inherited.file,
+                    old_function.span,
+                ),
+            }
+        }
+
+        for new_inherited in added {
+            if !renamed.contains(&new_inherited.function.name) {
+                self.push(
+                    Compatibility::Safe,
+                    format!(
+                        "Function `{name}.{}` was added",
+                        new_inherited.function.name
+                    ),
+                    new_inherited.file,
+                    new_inherited.function.span,
+                );
+            }
+        }
+    }
+
+    fn check_function(
+        &mut self,
+        interface: &str,
+        old: &Function,
+        old_file: &ValidatedFile,
+        new: &Function,
+        new_file: &ValidatedFile,
+    ) {
+        let name = format!("{interface}.{}", new.name);
+
+        match (param_parts(&old.param), param_parts(&new.param)) {
+            (Some((old_stream, old_param)), Some((new_stream, new_param))) => {
+                if old_stream != new_stream {
+                    let change = if new_stream { "now" } else { "no longer" };
+                    self.push(
+                        Compatibility::WireBreaking,
+                        format!("Parameter of `{name}` is {change} a stream"),
+                        new_file,
+                        new.span,
+                    );
+                }
+
+                let old_param = type_signature(&old_param, old_file);
+                let new_param = type_signature(&new_param, new_file);
+                if old_param != new_param {
+                    self.push(
+                        Compatibility::WireBreaking,
+                        format!(
+                            "Parameter of `{name}` changed type from `{old_param}` to `{new_param}`"
+                        ),
+                        new_file,
+                        new.span,
+                    );
+                }
+            }
+            (Some(_), None) => self.push(
+                Compatibility::WireBreaking,
+                format!("Parameter of `{name}` was removed"),
+                new_file,
+                new.span,
+            ),
+            (None, Some(_)) => self.push(
+                Compatibility::WireBreaking,
+                format!("Parameter of `{name}` was added"),
+                new_file,
+                new.span,
+            ),
+            (None, None) => {}
+        }
+
+        match (
+            old.return_type.as_ref().map(return_parts),
+            new.return_type.as_ref().map(return_parts),
+        ) {
+            (Some((old_stream, old_return)), Some((new_stream, new_return))) => {
+                if old_stream != new_stream {
+                    let change = if new_stream { "now" } else { "no longer" };
+                    self.push(
+                        Compatibility::WireBreaking,
+                        format!("Return of `{name}` is {change} a stream"),
+                        new_file,
+                        new.span,
+                    );
+                }
+
+                let old_return = type_signature(old_return, old_file);
+                let new_return = type_signature(new_return, new_file);
+                if old_return != new_return {
+                    self.push(
+                        Compatibility::WireBreaking,
+                        format!(
+                            "Return of `{name}` changed type from `{old_return}` to `{new_return}`"
+                        ),
+                        new_file,
+                        new.span,
+                    );
+                }
+            }
+            (Some(_), None) => self.push(
+                Compatibility::WireBreaking,
+                format!("Return of `{name}` was removed"),
+                new_file,
+                new.span,
+            ),
+            (None, Some(_)) => self.push(
+                Compatibility::WireBreaking,
+                format!("Return of `{name}` was added"),
+                new_file,
+                new.span,
+            ),
+            (None, None) => {}
+        }
+
+        // Declared errors are told apart by their position, so only the end of the list may change.
+        let old_throws = throws_signature(old, old_file);
+        let new_throws = throws_signature(new, new_file);
+        if new_throws.starts_with(&old_throws) {
+            for added in &new_throws[old_throws.len()..] {
+                self.push(
+                    Compatibility::SourceBreaking,
+                    format!("Function `{name}` now throws `{added}`"),
+                    new_file,
+                    new.span,
+                );
+            }
+        } else if old_throws.starts_with(&new_throws) {
+            for removed in &old_throws[new_throws.len()..] {
+                self.push(
+                    Compatibility::SourceBreaking,
+                    format!("Function `{name}` no longer throws `{removed}`"),
+                    new_file,
+                    new.span,
+                );
+            }
+        } else {
+            self.push(
+                Compatibility::WireBreaking,
+                format!(
+                    "Errors of `{name}` changed from `{}` to `{}`",
+                    old_throws.join(" | "),
+                    new_throws.join(" | ")
+                ),
+                new_file,
+                new.span,
+            );
+        }
+    }
+}
+
+fn qualify(file: &ValidatedFile, name: &str) -> String {
+    match &file.file.package {
+        Some(package) => package.qualify(name),
+        None => name.to_owned(),
+    }
+}
+
+fn can_be_missing(field: &SchemaField) -> bool {
+    matches!(field.ty, Type::Option(_)) || field.default.is_some()
+}
+
+/// Changes to named arguments are reported through their request schema.
+fn param_parts(param: &FunctionParam) -> Option<(bool, Type)> {
+    param
+        .ty()
+        .map(|ty| (matches!(param, FunctionParam::Stream(_)), ty))
+}
+
+fn return_parts(return_type: &FunctionReturn) -> (bool, &Type) {
+    match return_type {
+        FunctionReturn::Stream(ty) => (true, ty),
+        FunctionReturn::Simple(ty) => (false, ty),
+    }
+}
+
+/// Glass spelling of a type, with every reference fully qualified so that two
+/// spellings of the same type compare equal.
+fn type_signature(ty: &Type, file: &ValidatedFile) -> String {
+    match ty {
+        Type::Primitive(primitive) => primitive.to_string(),
+        Type::Option(option) => format!("option<{}>", type_signature(&option.inner, file)),
+        Type::Vector(vector) => format!("vec<{}>", type_signature(&vector.inner, file)),
+        Type::Map(map) => format!(
+            "map<{}, {}>",
+            type_signature(&map.key, file),
+            type_signature(&map.value, file)
+        ),
+        Type::Schema(schema_ref) => ref_signature(schema_ref, file),
+        Type::Generic(generic) => format!(
+            "{}<{}>",
+            ref_signature(&generic.schema, file),
+            generic
+                .args
+                .iter()
+                .map(|arg| type_signature(arg, file))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn ref_signature(schema_ref: &SchemaRef, file: &ValidatedFile) -> String {
+    match file.resolve(schema_ref) {
+        Ok(resolved) => qualify(resolved.import.unwrap_or(file), schema_ref.name()),
+        Err(_) => schema_ref.path.clone(),
+    }
+}
+
+fn parents_signature(interface: &Interface, file: &ValidatedFile) -> BTreeSet<String> {
+    interface
+        .extends
+        .iter()
+        .map(|parent_ref| match file.resolve_interface(parent_ref) {
+            Ok(parent) => qualify(parent.import.unwrap_or(file), parent_ref.name()),
+            Err(_) => parent_ref.path.clone(),
+        })
+        .collect()
+}
+
+fn throws_signature(function: &Function, file: &ValidatedFile) -> Vec<String> {
+    function
+        .throws
+        .iter()
+        .map(|error_ref| ref_signature(error_ref, file))
+        .collect()
+}
+
+fn function_signature(function: &Function, file: &ValidatedFile) -> String {
+    let stream = |is_stream: bool| if is_stream { "stream " } else { "" };
+
+    let mut signature = match param_parts(&function.param) {
+        Some((param_stream, param)) => {
+            format!("({}{})", stream(param_stream), type_signature(&param, file))
+        }
+        None => "()".to_owned(),
+    };
+    if let Some((return_stream, return_type)) = function.return_type.as_ref().map(return_parts) {
+        signature.push_str(&format!(
+            " -> {}{}",
+            stream(return_stream),
+            type_signature(return_type, file)
+        ));
+    }
+
+    signature
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::schema::FieldKeys;
+    use crate::compatibility::{Compatibility, check_compatibility};
+    use crate::project::Project;
+    use tempfile::{Builder, TempDir};
+
+    /// Helper to load a project from a temporary directory holding a single Glass file.
+    fn load(prefix: &str, content: &str) -> (Project, TempDir) {
+        let temp_dir = Builder::new().prefix(prefix).tempdir().unwrap();
+        let path = temp_dir.path().join("api.glass");
+        std::fs::write(&path, content).unwrap();
+
+        let project = Project::load(&[path]).unwrap();
+        (project, temp_dir)
+    }
+
+    fn changes(old: &str, new: &str) -> Vec<(Compatibility, String)> {
+        keyed_changes(old, new, FieldKeys::Names)
+    }
+
+    fn keyed_changes(old: &str, new: &str, field_keys: FieldKeys) -> Vec<(Compatibility, String)> {
+        let (old, _old_dir) = load("compat_old", old);
+        let (new, _new_dir) = load("compat_new", new);
+
+        check_compatibility(&old, &new, field_keys)
+            .into_iter()
+            .map(|change| (change.compatibility, change.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_compatibility_schemas() {
+        let changes = changes(
+            r#"
+                schema User { id: u64; name: string; email: option<string>; }
+                schema Team { id: u64; }
+            "#,
+            r#"
+                schema User { id: u32; email: option<string>; nickname: option<string>; age: u8; }
+            "#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Compatibility::SourceBreaking,
+                    "Schema `Team` was removed".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Field `User.id` changed type from `u64` to `u32`".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Field `User.name` was removed".to_owned()
+                ),
+                (
+                    Compatibility::Safe,
+                    "Field `User.nickname` was added".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Field `User.age` was added".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_numbered_rename() {
+        let old = "schema User { id: u64 = 1; name: string = 2; }";
+        let new = "schema User { id: u64 = 1; display_name: string = 2; }";
+
+        assert_eq!(
+            keyed_changes(old, new, FieldKeys::Numbers),
+            vec![(
+                Compatibility::SourceBreaking,
+                "Field `User.name` was renamed to `display_name`".to_owned()
+            )]
+        );
+
+        // Fields keyed by name are told apart by it, whatever their number.
+        assert_eq!(
+            keyed_changes(old, new, FieldKeys::Names),
+            vec![
+                (
+                    Compatibility::WireBreaking,
+                    "Field `User.name` was removed".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Field `User.display_name` was added".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_numbering() {
+        let old = "schema User { id: u64; }";
+        let new = "schema User { id: u64 = 1; }";
+        let numbered = |compatibility| {
+            vec![(
+                compatibility,
+                "Schema `User` changed whether its fields are numbered".to_owned(),
+            )]
+        };
+
+        assert_eq!(
+            keyed_changes(old, new, FieldKeys::Numbers),
+            numbered(Compatibility::WireBreaking)
+        );
+        assert_eq!(
+            keyed_changes(old, new, FieldKeys::Names),
+            numbered(Compatibility::Safe)
+        );
+    }
+
+    #[test]
+    fn test_compatibility_constants_and_defaults() {
+        let changes = changes(
+            r#"
+                const MAX_RETRIES: u32 = 10;
+                const API_VERSION: string = "v1";
+                schema Config { retries: u32 default 3; }
+            "#,
+            r#"
+                const MAX_RETRIES: u64 = 10;
+                const API_VERSION: string = "v2";
+                schema Config { retries: u32 default 5; timeout: duration default 30s; }
+            "#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Compatibility::Safe,
+                    "Constant `API_VERSION` changed value from `\"v1\"` to `\"v2\"`".to_owned()
+                ),
+                (
+                    Compatibility::SourceBreaking,
+                    "Constant `MAX_RETRIES` changed type from `u32` to `u64`".to_owned()
+                ),
+                (
+                    Compatibility::Safe,
+                    "Field `Config.retries` changed its default from `3` to `5`".to_owned()
+                ),
+                (
+                    Compatibility::Safe,
+                    "Field `Config.timeout` was added".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_interfaces() {
+        let changes = changes(
+            r#"
+                schema User { id: u64; }
+
+                interface Users {
+                    fn get(u64) -> User;
+                    fn list(u64) -> stream User;
+                    fn remove(u64);
+                    fn ping();
+                    fn withdraw(u64) -> User throws Locked | Empty;
+                    fn deposit(u64) throws Locked;
+                }
+
+                schema Locked { reason: string; }
+                schema Empty { balance: u64; }
+                schema Frozen { until: u64; }
+            "#,
+            r#"
+                schema User { id: u64; }
+
+                interface Users {
+                    fn fetch(u64) -> User;
+                    fn list(stream u64) -> stream User;
+                    fn count(u64) -> u64;
+                    fn ping(u64);
+                    fn withdraw(u64) -> User throws Empty | Locked | Frozen;
+                    fn deposit(u64) throws Locked | Frozen;
+                }
+
+                schema Locked { reason: string; }
+                schema Empty { balance: u64; }
+                schema Frozen { until: u64; }
+            "#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Compatibility::WireBreaking,
+                    "Function `Users.get` was renamed to `fetch`".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Parameter of `Users.list` is now a stream".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Function `Users.remove` was removed".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Parameter of `Users.ping` was added".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Errors of `Users.withdraw` changed from `Locked | Empty` to `Empty | Locked | Frozen`"
+                        .to_owned()
+                ),
+                (
+                    Compatibility::SourceBreaking,
+                    "Function `Users.deposit` now throws `Frozen`".to_owned()
+                ),
+                (
+                    Compatibility::Safe,
+                    "Function `Users.count` was added".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_inheritance() {
+        let changes = changes(
+            r#"
+                interface Health { fn ping(); }
+                interface Admin { fn reset(); }
+                interface Billing extends Health, Admin { fn charge(u64); }
+            "#,
+            r#"
+                interface Health { fn ping(); }
+                interface Admin { fn reset(); }
+                interface Billing extends Health { fn charge(u64); }
+            "#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Compatibility::SourceBreaking,
+                    "Interface `Billing` no longer extends `Admin`".to_owned()
+                ),
+                (
+                    Compatibility::WireBreaking,
+                    "Function `Billing.reset` was removed".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_generic_schemas() {
+        let changes = changes(
+            r#"
+                schema Page<T> { items: vec<T>; }
+                schema Listing { users: Page<u64>; }
+            "#,
+            r#"
+                schema Page<T, C> { items: vec<T>; cursor: option<C>; }
+                schema Listing { users: Page<u64, string>; }
+            "#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Compatibility::WireBreaking,
+                    "Field `Listing.users` changed type from `Page<u64>` to `Page<u64, string>`"
+                        .to_owned()
+                ),
+                (
+                    Compatibility::SourceBreaking,
+                    "Schema `Page` changed type parameters from `<T>` to `<T, C>`".to_owned()
+                ),
+                (
+                    Compatibility::Safe,
+                    "Field `Page.cursor` was added".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compatibility_unchanged() {
+        let content = r#"
+            package acme.users.v1;
+
+            enum Status { Active; }
+            schema User { id: u64; status: acme.users.v1.Status; }
+        "#;
+        let changes = changes(content, &content.replace("acme.users.v1.Status", "Status"));
+
+        assert!(changes.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::ast::span::Span;
+use crate::compatibility::{Change, Compatibility};
+use crate::error::ParserError;
+use crate::project::error::ProjectError;
+use crate::validator::error::{ValidatorError, ValidatorWarning};
+use pest::error::InputLocation;
+
+/// How serious a [Diagnostic] is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+/// Whether a [Label] points at the cause of a [Diagnostic] or only gives context.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LabelStyle {
+    Primary,
+    Secondary,
+}
+
+/// A span of source code annotated with a message.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub style: LabelStyle,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Label {
+    pub fn primary(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            style: LabelStyle::Primary,
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn secondary(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            style: LabelStyle::Secondary,
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// A problem found in a Glass file
+///
+/// It only holds spans, so rendering it with source snippets is up to the caller,
+/// which is expected to read the file the diagnostic refers to.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            labels: vec![],
+            hint: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(message)
+        }
+    }
+
+    pub fn note(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Note,
+            ..Self::error(message)
+        }
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+impl From<&ParserError> for Diagnostic {
+    fn from(error: &ParserError) -> Self {
+        match error {
+            ParserError::Pest(error) => {
+                let span = match error.location {
+                    InputLocation::Pos(position) => Span::new(position, position),
+                    InputLocation::Span((start, end)) => Span::new(start, end),
+                };
+
+                Diagnostic::error("Failed to parse the Glass file")
+                    .with_label(Label::primary(span, error.variant.message()))
+            }
+            ParserError::NoNextToken(span) => Diagnostic::error(error.to_string())
+                .with_label(Label::primary(*span, "while parsing this"))
+                .with_hint("this is a bug in the Glass parser, please report it"),
+            ParserError::InvalidFieldNumber(span) => Diagnostic::error(error.to_string())
+                .with_label(Label::primary(*span, "field number out of range")),
+            ParserError::InvalidLiteral(span) => Diagnostic::error(error.to_string())
+                .with_label(Label::primary(*span, "literal out of range")),
+            _ => Diagnostic::error(error.to_string()),
+        }
+    }
+}
+
+impl From<&ValidatorError> for Diagnostic {
+    fn from(error: &ValidatorError) -> Self {
+        let diagnostic = Diagnostic::error(error.to_string());
+        match error {
+            ValidatorError::DuplicateSchema { span, previous, .. }
+            | ValidatorError::DuplicateEnum { span, previous, .. }
+            | ValidatorError::DuplicateUnion { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "declared again here"))
+                .with_label(Label::secondary(*previous, "first declared here"))
+                .with_hint(
+                    "schemas, enums and unions share the same namespace, rename one of them",
+                ),
+            ValidatorError::DuplicateInterface { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "declared again here"))
+                .with_label(Label::secondary(*previous, "first declared here"))
+                .with_hint("rename one of the interfaces"),
+            ValidatorError::DuplicateConstant { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "declared again here"))
+                .with_label(Label::secondary(*previous, "first declared here"))
+                .with_hint("rename one of the constants"),
+            ValidatorError::InvalidConstant { span, .. } => {
+                diagnostic.with_label(Label::primary(*span, "invalid value"))
+            }
+            ValidatorError::DuplicateField { span, previous, .. }
+            | ValidatorError::DuplicateVariant { span, previous, .. }
+            | ValidatorError::DuplicateUnionVariant { span, previous, .. }
+            | ValidatorError::DuplicateFunction { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "declared again here"))
+                .with_label(Label::secondary(*previous, "first declared here")),
+            ValidatorError::DuplicateFieldNumber { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "number used again here"))
+                .with_label(Label::secondary(*previous, "first used here")),
+            ValidatorError::ReservedFieldNumber { span, reserved, .. }
+            | ValidatorError::ReservedFieldName { span, reserved, .. } => diagnostic
+                .with_label(Label::primary(*span, "used here"))
+                .with_label(Label::secondary(*reserved, "reserved here"))
+                .with_hint("reserved numbers and names belonged to removed fields, pick new ones"),
+            ValidatorError::MissingFieldNumber { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "missing a field number"))
+                .with_hint("either every field of a schema has a number, or none does"),
+            ValidatorError::InvalidDefault { span, .. } => {
+                diagnostic.with_label(Label::primary(*span, "invalid default value"))
+            }
+            ValidatorError::DuplicateAnnotation { span, previous, .. } => diagnostic
+                .with_label(Label::primary(*span, "used again here"))
+                .with_label(Label::secondary(*previous, "first used here")),
+            ValidatorError::InvalidAnnotation { span, .. } => {
+                diagnostic.with_label(Label::primary(*span, "invalid annotation"))
+            }
+            ValidatorError::InvalidThrows { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "invalid error"))
+                .with_hint("functions throw schemas, each at most once"),
+            ValidatorError::InvalidMapKey { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "invalid map key"))
+                .with_hint("use a string, an integer, a bool, a well-known type or an enum as key"),
+            ValidatorError::InvalidTypeParameter { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "declared by this schema"))
+                .with_hint("rename the type parameter"),
+            ValidatorError::InvalidTypeArguments { expected, span, .. } => diagnostic
+                .with_label(Label::primary(*span, format!("expects {expected} type arguments")))
+                .with_hint("generic schemas take one argument per type parameter, e.g. `Page<User>`, and other types take none"),
+            ValidatorError::InvalidUnionPayload { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "not a schema"))
+                .with_hint("wrap the value in a schema, as every variant of a union holds one"),
+            ValidatorError::SchemaNotFound(schema_ref) => {
+                let hint = match schema_ref.package() {
+                    Some(package) => format!("import a file declaring `package {package};`"),
+                    None => "declare it in this file, or import the file declaring it in the same package".to_owned(),
+                };
+
+                diagnostic
+                    .with_label(Label::primary(schema_ref.span, "not found in this scope"))
+                    .with_hint(hint)
+            }
+            ValidatorError::InterfaceNotFound(interface_ref) => {
+                let hint = match interface_ref.package() {
+                    Some(package) => format!("import a file declaring `package {package};`"),
+                    None => "declare it in this file, or import the file declaring it in the same package".to_owned(),
+                };
+
+                diagnostic
+                    .with_label(Label::primary(
+                        interface_ref.span,
+                        "not found in this scope",
+                    ))
+                    .with_hint(hint)
+            }
+            ValidatorError::CyclicInterface { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "extends itself through its parents"))
+                .with_hint("remove one of the `extends` along the cycle"),
+            ValidatorError::ConflictingFunction { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "conflicting function"))
+                .with_hint("rename one of the functions"),
+            ValidatorError::AmbiguousReference(schema_ref) => diagnostic
+                .with_label(Label::primary(
+                    schema_ref.span,
+                    "declared by more than one import",
+                ))
+                .with_hint("use the fully qualified name of the type"),
+            ValidatorError::InfiniteType { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "recursive without indirection"))
+                .with_hint("make one of the fields along the cycle an option or a vector"),
+        }
+    }
+}
+
+impl From<&ValidatorWarning> for Diagnostic {
+    fn from(warning: &ValidatorWarning) -> Self {
+        let diagnostic = Diagnostic::warning(warning.to_string());
+        match warning {
+            ValidatorWarning::UnusedImport { span, .. } => diagnostic
+                .with_label(Label::primary(*span, "imported here"))
+                .with_hint("remove the import, or reference one of its declarations"),
+        }
+    }
+}
+
+impl From<&Change> for Diagnostic {
+    fn from(change: &Change) -> Self {
+        match change.compatibility {
+            Compatibility::WireBreaking => Diagnostic::error(&change.message)
+                .with_label(Label::primary(change.span, "breaks existing peers"))
+                .with_hint("older peers can no longer exchange messages with newer ones"),
+            Compatibility::SourceBreaking => Diagnostic::warning(&change.message)
+                .with_label(Label::primary(change.span, "breaks existing code"))
+                .with_hint("payloads stay compatible, but the generated code changed"),
+            Compatibility::Safe => Diagnostic::note(&change.message)
+                .with_label(Label::primary(change.span, "compatible change")),
+        }
+    }
+}
+
+impl ProjectError {
+    /// Diagnostics describing the error.
+    ///
+    /// A validation error yields the whole report of the file, warnings included.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            ProjectError::ImportNotFound { span, .. } => vec![
+                Diagnostic::error(self.to_string())
+                    .with_label(Label::primary(*span, "imported here"))
+                    .with_hint("import paths are relative to the importing file"),
+            ],
+            ProjectError::ImportCycle(_) => vec![
+                Diagnostic::error(self.to_string())
+                    .with_hint("move the shared declarations to a file both can import"),
+            ],
+            ProjectError::ModuleConflict { .. } => {
+                vec![Diagnostic::error(self.to_string()).with_hint(
+                    "rename one of the files, as each is generated as a module named after it",
+                )]
+            }
+            ProjectError::Parser { error, .. } => vec![Diagnostic::from(error)],
+            ProjectError::Validator { report, .. } => report.diagnostics(),
+        }
+    }
+}
@@ -8,8 +8,14 @@ pub enum ParserError {
     #[error("The specified file was not a valid glass file: {0:?}")]
     UnexpectedRule(crate::parser::Rule),
 
-    #[error("The next token was not found")]
-    NoNextToken,
+    #[error("The next token was not found at {0}")]
+    NoNextToken(crate::ast::span::Span),
+
+    #[error("Field numbers must fit in an unsigned 32 bits integer, found at {0}")]
+    InvalidFieldNumber(crate::ast::span::Span),
+
+    #[error("The literal is out of range at {0}")]
+    InvalidLiteral(crate::ast::span::Span),
 
     #[error("An IO operation failed: {0}")]
     Io(#[from] std::io::Error),
@@ -1,5 +1,8 @@
 pub mod ast;
+pub mod compatibility;
+pub mod diagnostic;
 pub mod error;
 mod parser;
 pub mod prelude;
+pub mod project;
 pub mod validator;
@@ -1,5 +1,9 @@
 pub use crate::ast::File;
+pub use crate::diagnostic::Diagnostic;
 pub use crate::error::*;
+pub use crate::project::Project;
+pub use crate::project::error::ProjectError;
 pub use crate::validator::ValidatedFile;
+pub use crate::validator::report::ValidationReport;
 
 pub type ParserResult<T> = Result<T, ParserError>;
@@ -0,0 +1,49 @@
+use crate::ast::span::Span;
+use crate::error::ParserError;
+use crate::validator::report::ValidationReport;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ProjectError {
+    #[error("File `{file:?}` imports `{import}`, which was not found")]
+    ImportNotFound {
+        file: PathBuf,
+        import: String,
+        span: Span,
+    },
+
+    #[error("An import cycle was detected: {0:?}")]
+    ImportCycle(Vec<PathBuf>),
+
+    #[error("Files `{first:?}` and `{second:?}` are both generated as the `{module}` module")]
+    ModuleConflict {
+        module: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+
+    #[error("File `{path:?}` failed to be parsed: {error}")]
+    Parser { path: PathBuf, error: ParserError },
+
+    #[error("File `{path:?}` failed to be validated with {report}")]
+    Validator {
+        path: PathBuf,
+        report: ValidationReport,
+    },
+}
+
+impl ProjectError {
+    /// Path of the file the error was found in.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            ProjectError::ImportNotFound { file, .. } => Some(file),
+            ProjectError::ImportCycle(cycle) => cycle.last().map(PathBuf::as_path),
+            ProjectError::ModuleConflict { second, .. } => Some(second),
+            ProjectError::Parser { path, .. } => Some(path),
+            ProjectError::Validator { path, .. } => Some(path),
+        }
+    }
+}
+
+pub type ProjectResult<T> = Result<T, ProjectError>;
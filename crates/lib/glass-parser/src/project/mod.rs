@@ -0,0 +1,355 @@
+pub mod error;
+
+use crate::ast::import::Import;
+use crate::prelude::*;
+use crate::project::error::{ProjectError, ProjectResult};
+use crate::validator::error::ValidatorWarning;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// A set of Glass files validated together, with their imports resolved.
+///
+/// Files are stored in dependency order, so every file comes after the files it imports.
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub files: Vec<Arc<ValidatedFile>>,
+}
+
+impl Project {
+    /// Loads the given files along with every file they import, directly or not.
+    #[tracing::instrument(skip_all)]
+    pub fn load(paths: &[PathBuf]) -> ProjectResult<Self> {
+        info!("Project loading has begun");
+
+        let mut loader = Loader::default();
+        for path in paths {
+            let path = path.canonicalize().map_err(|error| ProjectError::Parser {
+                path: path.clone(),
+                error: ParserError::Io(error),
+            })?;
+            loader.load(&path)?;
+        }
+        Self::check_module_names(&loader.files)?;
+
+        Ok(Self {
+            files: loader.files,
+        })
+    }
+
+    /// Every file is generated as a module named after it, next to the others, so no two
+    /// files may share a module name.
+    fn check_module_names(files: &[Arc<ValidatedFile>]) -> ProjectResult<()> {
+        let mut modules: HashMap<String, &Path> = HashMap::with_capacity(files.len());
+        for file in files {
+            let module = file.file.module_name();
+            if let Some(first) = modules.insert(module.clone(), &file.file.path) {
+                error!(?module, "Conflicting module names detected");
+                return Err(ProjectError::ModuleConflict {
+                    module,
+                    first: first.to_path_buf(),
+                    second: file.file.path.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds a loaded file by its canonical path.
+    pub fn file(&self, path: &Path) -> Option<&Arc<ValidatedFile>> {
+        self.files.iter().find(|file| file.file.path == path)
+    }
+
+    /// Warnings of every loaded file, along with the path of the file declaring them.
+    pub fn warnings(&self) -> impl Iterator<Item = (&Path, &ValidatorWarning)> {
+        self.files.iter().flat_map(|file| {
+            file.warnings
+                .iter()
+                .map(|warning| (file.file.path.as_path(), warning))
+        })
+    }
+}
+
+/// Depth-first import walker.
+///
+/// `stack` holds the files currently being loaded, which is what allows detecting cycles.
+#[derive(Default)]
+struct Loader {
+    files: Vec<Arc<ValidatedFile>>,
+    loaded: HashMap<PathBuf, usize>,
+    stack: Vec<PathBuf>,
+}
+
+impl Loader {
+    fn load(&mut self, path: &Path) -> ProjectResult<Arc<ValidatedFile>> {
+        if let Some(&index) = self.loaded.get(path) {
+            return Ok(self.files[index].clone());
+        }
+
+        if let Some(position) = self.stack.iter().position(|entry| entry == path) {
+            let mut cycle = self.stack[position..].to_vec();
+            cycle.push(path.to_path_buf());
+            error!(?cycle, "Import cycle detected");
+            return Err(ProjectError::ImportCycle(cycle));
+        }
+
+        let mut file = File::try_new(path.to_path_buf()).map_err(|error| ProjectError::Parser {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        file.try_parse().map_err(|error| ProjectError::Parser {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        self.stack.push(path.to_path_buf());
+
+        let mut imports = Vec::with_capacity(file.imports.len());
+        for import in &file.imports {
+            let import_path = Self::resolve_import_path(path, import)?;
+            imports.push(self.load(&import_path)?);
+        }
+
+        self.stack.pop();
+
+        let validated_file =
+            ValidatedFile::validate_with_imports(file, imports).map_err(|report| {
+                ProjectError::Validator {
+                    path: path.to_path_buf(),
+                    report,
+                }
+            })?;
+
+        let validated_file = Arc::new(validated_file);
+        self.loaded.insert(path.to_path_buf(), self.files.len());
+        self.files.push(validated_file.clone());
+
+        Ok(validated_file)
+    }
+
+    /// Imports are relative to the directory of the file declaring them.
+    fn resolve_import_path(file_path: &Path, import: &Import) -> ProjectResult<PathBuf> {
+        let base = file_path.parent().unwrap_or(Path::new("."));
+        base.join(&import.path)
+            .canonicalize()
+            .map_err(|_| ProjectError::ImportNotFound {
+                file: file_path.to_path_buf(),
+                import: import.path.clone(),
+                span: import.span,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::schema::SchemaRef;
+    use crate::project::Project;
+    use crate::project::error::ProjectError;
+    use crate::validator::TypeKind;
+    use crate::validator::error::{ValidatorError, ValidatorWarning};
+    use std::path::{Path, PathBuf};
+    use tempfile::{Builder, TempDir};
+
+    /// Helper to create a temporary directory holding several Glass files.
+    fn create_temp_dir(prefix: &str, files: &[(&str, &str)]) -> TempDir {
+        let temp_dir = Builder::new().prefix(prefix).tempdir().unwrap();
+        for (name, content) in files {
+            let file_path = temp_dir.path().join(name);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(file_path, content).unwrap();
+        }
+
+        temp_dir
+    }
+
+    fn path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(name).canonicalize().unwrap()
+    }
+
+    #[test]
+    fn test_load_cross_file_reference() {
+        let temp_dir = create_temp_dir(
+            "project_cross_file",
+            &[
+                ("users.glass", "schema User { id: u64; }"),
+                (
+                    "billing.glass",
+                    r#"
+                        import "users.glass";
+
+                        interface Billing {
+                            fn charge(User);
+                        }
+                    "#,
+                ),
+            ],
+        );
+
+        let billing_path = path(temp_dir.path(), "billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+
+        // Imported files come first.
+        assert_eq!(project.files.len(), 2);
+        assert_eq!(
+            project.files[0].file.path,
+            path(temp_dir.path(), "users.glass")
+        );
+
+        let billing = project.file(&billing_path).unwrap();
+        let resolved = billing.resolve(&SchemaRef::new("User")).unwrap();
+        assert_eq!(resolved.kind, TypeKind::Schema);
+        assert!(resolved.import.is_some());
+    }
+
+    #[test]
+    fn test_load_module_conflict() {
+        let temp_dir = create_temp_dir(
+            "project_module_conflict",
+            &[
+                ("user-types.glass", "schema User { id: u64; }"),
+                ("user_types.glass", "schema Account { id: u64; }"),
+            ],
+        );
+
+        let result = Project::load(&[
+            path(temp_dir.path(), "user-types.glass"),
+            path(temp_dir.path(), "user_types.glass"),
+        ]);
+        let Err(ProjectError::ModuleConflict { module, .. }) = result else {
+            panic!("Expected a module conflict");
+        };
+        assert_eq!(module, "user_types");
+    }
+
+    #[test]
+    fn test_load_import_cycle() {
+        let temp_dir = create_temp_dir(
+            "project_cycle",
+            &[
+                ("a.glass", r#"import "b.glass";"#),
+                ("b.glass", r#"import "a.glass";"#),
+            ],
+        );
+
+        let result = Project::load(&[path(temp_dir.path(), "a.glass")]);
+        let Err(ProjectError::ImportCycle(cycle)) = result else {
+            panic!("Expected an import cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+    }
+
+    #[test]
+    fn test_load_import_not_found() {
+        let temp_dir = create_temp_dir("project_not_found", &[("a.glass", r#"import "b.glass";"#)]);
+
+        let result = Project::load(&[path(temp_dir.path(), "a.glass")]);
+        assert!(matches!(result, Err(ProjectError::ImportNotFound { .. })));
+    }
+
+    #[test]
+    fn test_load_imports_are_not_transitive() {
+        let temp_dir = create_temp_dir(
+            "project_transitive",
+            &[
+                ("users.glass", "schema User { id: u64; }"),
+                (
+                    "teams.glass",
+                    r#"import "users.glass"; schema Team { owner: User; }"#,
+                ),
+                (
+                    "billing.glass",
+                    r#"import "teams.glass"; schema Invoice { user: User; }"#,
+                ),
+            ],
+        );
+
+        let result = Project::load(&[path(temp_dir.path(), "billing.glass")]);
+        let Err(ProjectError::Validator { report, .. }) = result else {
+            panic!("Expected a validation error");
+        };
+        assert!(matches!(
+            report.errors.as_slice(),
+            [ValidatorError::SchemaNotFound(_)]
+        ));
+        // `teams.glass` is imported, but none of its declarations is referenced.
+        assert!(matches!(
+            report.warnings.as_slice(),
+            [ValidatorWarning::UnusedImport { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_load_unused_import_warning() {
+        let temp_dir = create_temp_dir(
+            "project_unused_import",
+            &[
+                ("users.glass", "schema User { id: u64; }"),
+                ("teams.glass", "schema Team { name: string; }"),
+                (
+                    "billing.glass",
+                    r#"
+                        import "users.glass";
+                        import "teams.glass";
+
+                        schema Invoice { user: User; }
+                    "#,
+                ),
+            ],
+        );
+
+        let project = Project::load(&[path(temp_dir.path(), "billing.glass")]).unwrap();
+        let warnings = project.warnings().collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 1);
+
+        let (warning_path, ValidatorWarning::UnusedImport { path: import, .. }) = warnings[0];
+        assert_eq!(warning_path, path(temp_dir.path(), "billing.glass"));
+        assert_eq!(import, "teams.glass");
+    }
+
+    #[test]
+    fn test_load_package_qualified_reference() {
+        let temp_dir = create_temp_dir(
+            "project_packages",
+            &[
+                (
+                    "users.glass",
+                    "package acme.users.v1; schema User { id: u64; } schema Error { code: u8; }",
+                ),
+                (
+                    "billing.glass",
+                    r#"
+                        package acme.billing.v1;
+
+                        import "users.glass";
+
+                        schema Error { reason: string; }
+
+                        schema Invoice {
+                            user: acme.users.v1.User;
+                            error: Error;
+                            user_error: acme.users.v1.Error;
+                        }
+                    "#,
+                ),
+            ],
+        );
+
+        let billing_path = path(temp_dir.path(), "billing.glass");
+        let project = Project::load(std::slice::from_ref(&billing_path)).unwrap();
+        let billing = project.file(&billing_path).unwrap();
+
+        let local = billing.resolve(&SchemaRef::new("Error")).unwrap();
+        assert!(local.import.is_none());
+
+        let imported = billing
+            .resolve(&SchemaRef::new("acme.users.v1.Error"))
+            .unwrap();
+        assert!(imported.import.is_some());
+
+        // Types of other packages are not visible without their qualifier.
+        assert!(billing.resolve(&SchemaRef::new("User")).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::literal::Literal;
+use crate::ast::schema::Schema;
+use crate::prelude::*;
+use crate::validator::constraints;
+use crate::validator::error::ValidatorError;
+use std::collections::HashMap;
+use tracing::error;
+
+/// Kind of declaration an annotation is attached to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnnotationTarget {
+    Constant,
+    Schema,
+    Field,
+    Enum,
+    Union,
+    /// A variant of an enum or of a union.
+    Variant,
+    Interface,
+    Function,
+}
+
+/// Annotations understood by Glass itself.
+///
+/// Any other annotation is accepted as is, and left to codegen plugins.
+pub const KNOWN_ANNOTATIONS: &[&str] = &[
+    "deprecated",
+    "idempotent",
+    "timeout",
+    "rust",
+    "len",
+    "range",
+    "pattern",
+    "non_empty",
+];
+
+pub fn is_known_annotation(name: &str) -> bool {
+    KNOWN_ANNOTATIONS.contains(&name)
+}
+
+/// Checks the annotations of every declaration of a file.
+pub(crate) fn validate_file_annotations(file: &File, errors: &mut Vec<ValidatorError>) {
+    for constant in &file.constants {
+        validate_annotations(&constant.annotations, AnnotationTarget::Constant, errors);
+    }
+
+    for schema in &file.schemas {
+        validate_annotations(&schema.annotations, AnnotationTarget::Schema, errors);
+        validate_default_derive(schema, errors);
+        for field in &schema.fields {
+            validate_annotations(&field.annotations, AnnotationTarget::Field, errors);
+            constraints::validate_field_constraints(field, errors);
+        }
+    }
+
+    for enumeration in &file.enums {
+        validate_annotations(&enumeration.annotations, AnnotationTarget::Enum, errors);
+        for variant in &enumeration.variants {
+            validate_annotations(&variant.annotations, AnnotationTarget::Variant, errors);
+        }
+    }
+
+    for union in &file.unions {
+        validate_annotations(&union.annotations, AnnotationTarget::Union, errors);
+        for variant in &union.variants {
+            validate_annotations(&variant.annotations, AnnotationTarget::Variant, errors);
+        }
+    }
+
+    for interface in &file.interfaces {
+        validate_annotations(&interface.annotations, AnnotationTarget::Interface, errors);
+        for function in &interface.functions {
+            validate_annotations(&function.annotations, AnnotationTarget::Function, errors);
+        }
+    }
+}
+
+fn validate_annotations(
+    annotations: &[Annotation],
+    target: AnnotationTarget,
+    errors: &mut Vec<ValidatorError>,
+) {
+    let mut annotation_spans = HashMap::new();
+    for annotation in annotations {
+        if !is_known_annotation(&annotation.name) {
+            continue;
+        }
+
+        if let Some(previous) = annotation_spans.insert(annotation.name.as_str(), annotation.span) {
+            error!(annotation = ?annotation.name, "Duplicate annotation detected");
+            errors.push(ValidatorError::DuplicateAnnotation {
+                name: annotation.name.clone(),
+                span: annotation.span,
+                previous,
+            });
+            continue;
+        }
+
+        if let Err(reason) = validate_annotation(annotation, target) {
+            error!(annotation = ?annotation.name, reason, "Invalid annotation detected");
+            errors.push(ValidatorError::InvalidAnnotation {
+                name: annotation.name.clone(),
+                reason,
+                span: annotation.span,
+            });
+        }
+    }
+}
+
+/// Checks a schema declaring field defaults doesn't derive `Default`, which would replace
+/// the implementation generated from them.
+fn validate_default_derive(schema: &Schema, errors: &mut Vec<ValidatorError>) {
+    if schema.fields.iter().all(|field| field.default.is_none()) {
+        return;
+    }
+
+    for annotation in schema.annotations.iter().filter(|a| a.name == "rust") {
+        if derives(annotation).any(is_default_derive) {
+            error!(schema = ?schema.name, "Default derived along with field defaults");
+            errors.push(ValidatorError::InvalidAnnotation {
+                name: annotation.name.clone(),
+                reason: "can't derive `Default` on a schema declaring field defaults".to_owned(),
+                span: annotation.span,
+            });
+        }
+    }
+}
+
+fn validate_annotation(annotation: &Annotation, target: AnnotationTarget) -> Result<(), String> {
+    match annotation.name.as_str() {
+        "deprecated" => match positional(annotation)?.as_slice() {
+            [] | [Literal::String(_)] => Ok(()),
+            _ => Err("takes an optional string explaining what to use instead".to_owned()),
+        },
+        "idempotent" => {
+            if target != AnnotationTarget::Function {
+                return Err("only applies to functions".to_owned());
+            }
+
+            match positional(annotation)?.as_slice() {
+                [] => Ok(()),
+                _ => Err("takes no argument".to_owned()),
+            }
+        }
+        "timeout" => {
+            if !matches!(
+                target,
+                AnnotationTarget::Function | AnnotationTarget::Interface
+            ) {
+                return Err("only applies to functions and interfaces".to_owned());
+            }
+
+            match positional(annotation)?.as_slice() {
+                [Literal::Duration(_)] => Ok(()),
+                _ => Err("takes a single duration, e.g. `5s`".to_owned()),
+            }
+        }
+        "rust" => {
+            if !matches!(
+                target,
+                AnnotationTarget::Schema | AnnotationTarget::Enum | AnnotationTarget::Union
+            ) {
+                return Err("only applies to schemas, enums and unions".to_owned());
+            }
+
+            if annotation.args.is_empty() {
+                return Err("takes at least one named argument".to_owned());
+            }
+
+            annotation
+                .args
+                .iter()
+                .try_for_each(|arg| match (arg.name.as_deref(), &arg.value) {
+                    (Some("derive"), Literal::String(derives)) => {
+                        if !derives.split(',').map(str::trim).all(is_rust_path) {
+                            return Err(
+                                "`derive` takes a comma separated list of Rust paths".to_owned()
+                            );
+                        }
+
+                        // Deriving `Default` on an enum needs a `#[default]` variant,
+                        // which is never generated.
+                        let is_default = derives.split(',').map(str::trim).any(is_default_derive);
+                        match is_default && target != AnnotationTarget::Schema {
+                            true => Err("can only derive `Default` on schemas".to_owned()),
+                            false => Ok(()),
+                        }
+                    }
+                    (Some("derive"), _) => Err("`derive` takes a string".to_owned()),
+                    (Some(name), _) => Err(format!("has no `{name}` argument")),
+                    (None, _) => Err("takes no positional argument".to_owned()),
+                })
+        }
+        name if constraints::is_constraint(name) => {
+            if target != AnnotationTarget::Field {
+                return Err("only applies to schema fields".to_owned());
+            }
+
+            // Arguments are checked along with the type of the field.
+            Ok(())
+        }
+        // Unknown annotations are never validated.
+        _ => Ok(()),
+    }
+}
+
+/// Paths listed by the `derive` arguments of a `@rust` annotation.
+pub(super) fn derives(annotation: &Annotation) -> impl Iterator<Item = &str> {
+    annotation
+        .args
+        .iter()
+        .filter(|arg| arg.name.as_deref() == Some("derive"))
+        .filter_map(|arg| match &arg.value {
+            Literal::String(derives) => Some(derives.split(',').map(str::trim)),
+            _ => None,
+        })
+        .flatten()
+}
+
+pub(super) fn is_default_derive(path: &str) -> bool {
+    path.rsplit("::").next() == Some("Default")
+}
+
+fn is_rust_path(path: &str) -> bool {
+    path.split("::").all(|segment| {
+        let mut chars = segment.chars();
+        chars
+            .next()
+            .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+            && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+    })
+}
+
+/// Values of an annotation only taking positional arguments.
+pub(super) fn positional(annotation: &Annotation) -> Result<Vec<&Literal>, String> {
+    match annotation.args.iter().find(|arg| arg.name.is_some()) {
+        Some(_) => Err("takes no named argument".to_owned()),
+        None => Ok(annotation.args.iter().map(|arg| &arg.value).collect()),
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::literal::Literal;
+use crate::ast::schema::SchemaField;
+use crate::ast::types::{PrimitiveType, Type};
+use crate::validator::annotations::positional;
+use crate::validator::defaults::{integer_range, is_integer_in_range};
+use crate::validator::error::ValidatorError;
+use tracing::error;
+
+/// Annotations constraining the values a field accepts, checked by the generated `validate`.
+const CONSTRAINTS: &[&str] = &["len", "range", "pattern", "non_empty"];
+
+pub(super) fn is_constraint(name: &str) -> bool {
+    CONSTRAINTS.contains(&name)
+}
+
+/// Checks the arguments of the constraints of a field against its type.
+///
+/// Constraints on an optional field apply to its value when present.
+pub(super) fn validate_field_constraints(field: &SchemaField, errors: &mut Vec<ValidatorError>) {
+    let ty = match &field.ty {
+        Type::Option(option_type) => &option_type.inner,
+        ty => ty,
+    };
+
+    for annotation in &field.annotations {
+        if !is_constraint(&annotation.name) {
+            continue;
+        }
+
+        if let Err(reason) = validate_constraint(annotation, ty) {
+            error!(field_name = ?field.name, annotation = ?annotation.name, reason, "Invalid constraint detected");
+            errors.push(ValidatorError::InvalidAnnotation {
+                name: annotation.name.clone(),
+                reason,
+                span: annotation.span,
+            });
+        }
+    }
+}
+
+fn validate_constraint(annotation: &Annotation, ty: &Type) -> Result<(), String> {
+    let args = positional(annotation)?;
+    match annotation.name.as_str() {
+        "len" | "non_empty" => {
+            if !has_length(ty) {
+                return Err("only applies to strings, bytes, vectors and maps".to_owned());
+            }
+
+            match (annotation.name.as_str(), args.as_slice()) {
+                ("non_empty", []) => Ok(()),
+                ("non_empty", _) => Err("takes no argument".to_owned()),
+                (_, [range @ Literal::Range { .. }]) => validate_range(range, Some((0, u128::MAX))),
+                _ => Err("takes a single range of lengths, e.g. `1..64`".to_owned()),
+            }
+        }
+        "range" => {
+            let Type::Primitive(primitive) = ty else {
+                return Err("only applies to integers and floats".to_owned());
+            };
+            let is_float = matches!(primitive, PrimitiveType::F32 | PrimitiveType::F64);
+            let bounds = integer_range(primitive);
+            if !is_float && bounds.is_none() {
+                return Err("only applies to integers and floats".to_owned());
+            }
+
+            match args.as_slice() {
+                [range @ Literal::Range { .. }] => validate_range(range, bounds),
+                _ => Err("takes a single range, e.g. `0..150`".to_owned()),
+            }
+        }
+        "pattern" => {
+            if !matches!(ty, Type::Primitive(PrimitiveType::String)) {
+                return Err("only applies to strings".to_owned());
+            }
+
+            match args.as_slice() {
+                [Literal::String(pattern)] => regex::Regex::new(pattern)
+                    .map(|_| ())
+                    .map_err(|error| format!("has an invalid regular expression: {error}")),
+                _ => Err("takes a single regular expression, e.g. `\"^[a-z]+$\"`".to_owned()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn has_length(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Primitive(PrimitiveType::String | PrimitiveType::Bytes)
+            | Type::Vector(_)
+            | Type::Map(_)
+    )
+}
+
+/// Bounds must be integers within `integer_bounds` when given, and the range must not be empty.
+fn validate_range(range: &Literal, integer_bounds: Option<(i128, u128)>) -> Result<(), String> {
+    let Literal::Range {
+        start,
+        end,
+        inclusive,
+    } = range
+    else {
+        return Ok(());
+    };
+    if start.is_none() && end.is_none() {
+        return Err("needs at least one bound".to_owned());
+    }
+
+    let mut values = Vec::new();
+    for bound in [start, end].into_iter().flatten() {
+        let value = match (bound.as_ref(), integer_bounds) {
+            (bound, Some(range)) if is_integer_in_range(bound, range) == Some(false) => {
+                return Err(format!("has a bound out of range: `{bound}`"));
+            }
+            (Literal::Integer(value), _) => *value as f64,
+            (Literal::LargeInteger(value), _) => *value as f64,
+            (Literal::Float(_), Some(_)) => {
+                return Err(format!("has a float bound on an integer: `{bound}`"));
+            }
+            (Literal::Float(value), None) => *value,
+            _ => return Err(format!("has an invalid bound: `{bound}`")),
+        };
+        values.push(value);
+    }
+
+    match values.as_slice() {
+        [start, end] if start > end || (start == end && !inclusive) => {
+            Err(format!("has an empty range: `{range}`"))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,158 @@
+use crate::ast::literal::Literal;
+use crate::ast::schema::{DefaultValue, Schema, SchemaRef};
+use crate::ast::types::{PrimitiveType, Type};
+use crate::validator::error::ValidatorError;
+use crate::validator::{ResolvedType, TypeKind, ValidatedFile, is_type_param};
+use tracing::error;
+
+/// Checks that the default value of every field is a valid value of its type.
+///
+/// Vectors and maps default to being empty, and the other types without a literal
+/// form, such as schemas or timestamps, cannot be given a default at all.
+pub(crate) fn validate_field_defaults(
+    validated_file: &ValidatedFile,
+    errors: &mut Vec<ValidatorError>,
+) {
+    for schema in &validated_file.file.schemas {
+        for field in &schema.fields {
+            let Some(default) = &field.default else {
+                continue;
+            };
+
+            if let Err(reason) = check_default(validated_file, schema, &field.ty, &default.value) {
+                error!(schema_name = ?schema.name, field_name = ?field.name, "Invalid default value");
+                errors.push(ValidatorError::InvalidDefault {
+                    schema: schema.name.clone(),
+                    field: field.name.clone(),
+                    value: default.value.to_string(),
+                    reason,
+                    span: default.span,
+                });
+            }
+        }
+    }
+}
+
+fn check_default(
+    validated_file: &ValidatedFile,
+    schema: &Schema,
+    ty: &Type,
+    value: &DefaultValue,
+) -> Result<(), String> {
+    match (ty, value) {
+        (Type::Option(_), DefaultValue::None) => Ok(()),
+        (Type::Option(option_type), value) => {
+            check_default(validated_file, schema, &option_type.inner, value)
+        }
+        (_, DefaultValue::None) => Err("is only valid for options".to_owned()),
+        (Type::Vector(_) | Type::Map(_), _) => {
+            Err("cannot be set on vectors and maps, which default to being empty".to_owned())
+        }
+        (Type::Primitive(primitive), DefaultValue::Literal(literal)) => {
+            check_literal(primitive, literal)
+        }
+        (Type::Primitive(primitive), DefaultValue::Variant(_)) => {
+            Err(format!("is not a valid `{primitive}`"))
+        }
+        (Type::Schema(schema_ref), _) if is_type_param(&schema.type_params, schema_ref) => {
+            Err("cannot be set on type parameters".to_owned())
+        }
+        (Type::Schema(schema_ref), value) => check_enum(validated_file, schema_ref, value),
+        (Type::Generic(_), _) => Err("cannot be set on schemas and unions".to_owned()),
+    }
+}
+
+/// Checks that a literal is a valid value of a primitive type, shared with constants.
+pub(super) fn check_literal(primitive: &PrimitiveType, literal: &Literal) -> Result<(), String> {
+    if let Some(range) = integer_range(primitive) {
+        return match is_integer_in_range(literal, range) {
+            Some(true) => Ok(()),
+            Some(false) => Err(format!("does not fit in `{primitive}`")),
+            None => Err(format!("is not a valid `{primitive}`")),
+        };
+    }
+
+    match (primitive, literal) {
+        (PrimitiveType::String, Literal::String(_))
+        | (PrimitiveType::Bool, Literal::Bool(_))
+        | (PrimitiveType::Duration, Literal::Duration(_))
+        | (
+            PrimitiveType::F32 | PrimitiveType::F64,
+            Literal::Float(_) | Literal::Integer(_) | Literal::LargeInteger(_),
+        ) => Ok(()),
+        (
+            PrimitiveType::Bytes
+            | PrimitiveType::Timestamp
+            | PrimitiveType::Ulid
+            | PrimitiveType::Uuid,
+            _,
+        ) => Err(format!(
+            "is not supported, as `{primitive}` has no literal form"
+        )),
+        _ => Err(format!("is not a valid `{primitive}`")),
+    }
+}
+
+/// Smallest and largest values of an integer type.
+///
+/// The largest is a `u128`, as the one of `u128` does not fit in an `i128`.
+pub(super) fn integer_range(primitive: &PrimitiveType) -> Option<(i128, u128)> {
+    let range = match primitive {
+        PrimitiveType::U8 => (0, u8::MAX.into()),
+        PrimitiveType::U16 => (0, u16::MAX.into()),
+        PrimitiveType::U32 => (0, u32::MAX.into()),
+        PrimitiveType::U64 => (0, u64::MAX.into()),
+        PrimitiveType::U128 => (0, u128::MAX),
+        PrimitiveType::I8 => (i8::MIN.into(), i8::MAX as u128),
+        PrimitiveType::I16 => (i16::MIN.into(), i16::MAX as u128),
+        PrimitiveType::I32 => (i32::MIN.into(), i32::MAX as u128),
+        PrimitiveType::I64 => (i64::MIN.into(), i64::MAX as u128),
+        PrimitiveType::I128 => (i128::MIN, i128::MAX as u128),
+        _ => return None,
+    };
+
+    Some(range)
+}
+
+/// Whether an integer literal is within a range of [integer_range], or `None` if it is not
+/// an integer at all.
+pub(super) fn is_integer_in_range(literal: &Literal, (min, max): (i128, u128)) -> Option<bool> {
+    match literal {
+        Literal::Integer(value) => Some(match u128::try_from(*value) {
+            Ok(value) => value <= max,
+            Err(_) => *value >= min,
+        }),
+        Literal::LargeInteger(value) => Some(*value <= max),
+        _ => None,
+    }
+}
+
+/// Named types only take a default when they are enums, in which case it must be one of the variants.
+fn check_enum(
+    validated_file: &ValidatedFile,
+    schema_ref: &SchemaRef,
+    value: &DefaultValue,
+) -> Result<(), String> {
+    // Unresolved references are already reported on their own.
+    let Ok(ResolvedType { kind, import }) = validated_file.resolve(schema_ref) else {
+        return Ok(());
+    };
+    if kind != TypeKind::Enum {
+        return Err("cannot be set on schemas and unions".to_owned());
+    }
+
+    let declaring_file = import.unwrap_or(validated_file);
+    let enumeration = &declaring_file.enum_map[schema_ref.name()];
+    match value {
+        DefaultValue::Variant(variant)
+            if enumeration
+                .variants
+                .iter()
+                .any(|other| &other.name == variant) =>
+        {
+            Ok(())
+        }
+        DefaultValue::Variant(_) => Err(format!("is not a variant of `{}`", enumeration.name)),
+        _ => Err(format!("must be a variant of `{}`", enumeration.name)),
+    }
+}
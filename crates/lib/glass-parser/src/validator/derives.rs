@@ -0,0 +1,212 @@
+use crate::ast::annotation::Annotation;
+use crate::ast::schema::{Schema, SchemaRef};
+use crate::ast::types::{PrimitiveType, Type};
+use crate::validator::annotations::{derives, is_default_derive};
+use crate::validator::error::ValidatorError;
+use crate::validator::{TypeKind, ValidatedFile};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::error;
+
+/// Traits requested through `@rust(derive = "..")` that only build when every value held
+/// by the declaration implements them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Derive {
+    Default,
+    Eq,
+    Hash,
+}
+
+impl Derive {
+    fn from_path(path: &str) -> Option<Self> {
+        if is_default_derive(path) {
+            return Some(Self::Default);
+        }
+
+        match path.rsplit("::").next() {
+            Some("Eq") => Some(Self::Eq),
+            Some("Hash") => Some(Self::Hash),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Derive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Derive::Default => "Default",
+            Derive::Eq => "Eq",
+            Derive::Hash => "Hash",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Checks that the fields of schemas and the payloads of unions implement the traits
+/// they request to derive.
+///
+/// Maps are taken as hash maps, the kind of map being left to codegen, so deriving `Hash`
+/// is rejected along with them.
+pub(crate) fn validate_derives(validated_file: &ValidatedFile, errors: &mut Vec<ValidatorError>) {
+    for schema in &validated_file.file.schemas {
+        for (annotation, derive) in requested_derives(&schema.annotations) {
+            let mut checker = Checker::new(validated_file, &schema.name);
+            let unsupported = schema
+                .fields
+                .iter()
+                .find(|field| !checker.check_type(&field.ty, validated_file, derive));
+
+            if let Some(field) = unsupported {
+                error!(schema = ?schema.name, field = ?field.name, %derive, "Unsupported derive");
+                errors.push(ValidatorError::InvalidAnnotation {
+                    name: annotation.name.clone(),
+                    reason: format!(
+                        "can't derive `{derive}`, which field `{}` doesn't implement",
+                        field.name
+                    ),
+                    span: annotation.span,
+                });
+            }
+        }
+    }
+
+    for union in &validated_file.file.unions {
+        for (annotation, derive) in requested_derives(&union.annotations) {
+            let mut checker = Checker::new(validated_file, &union.name);
+            let unsupported = union.variants.iter().find(|variant| {
+                !checker.check_declaration(&variant.payload, validated_file, derive)
+            });
+
+            if let Some(variant) = unsupported {
+                error!(union = ?union.name, variant = ?variant.name, %derive, "Unsupported derive");
+                errors.push(ValidatorError::InvalidAnnotation {
+                    name: annotation.name.clone(),
+                    reason: format!(
+                        "can't derive `{derive}`, which variant `{}` doesn't implement",
+                        variant.name
+                    ),
+                    span: annotation.span,
+                });
+            }
+        }
+    }
+}
+
+/// Derives checked against the values of a declaration, along with the annotation
+/// requesting them.
+fn requested_derives(annotations: &[Annotation]) -> impl Iterator<Item = (&Annotation, Derive)> {
+    annotations
+        .iter()
+        .filter(|annotation| annotation.name == "rust")
+        .flat_map(|annotation| {
+            derives(annotation)
+                .filter_map(Derive::from_path)
+                .map(move |derive| (annotation, derive))
+        })
+}
+
+/// Walks the types held by a declaration, through the schemas and unions they refer to.
+struct Checker {
+    /// Declarations already checked, by the path of their file and their name.
+    visited: HashSet<(PathBuf, String)>,
+}
+
+impl Checker {
+    fn new(validated_file: &ValidatedFile, name: &str) -> Self {
+        let visited = HashSet::from([(validated_file.file.path.clone(), name.to_owned())]);
+        Self { visited }
+    }
+
+    fn check_type(&mut self, ty: &Type, file: &ValidatedFile, derive: Derive) -> bool {
+        match ty {
+            Type::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => derive == Derive::Default,
+            Type::Primitive(PrimitiveType::Timestamp) => derive != Derive::Default,
+            Type::Primitive(_) => true,
+            Type::Option(option) => {
+                derive == Derive::Default || self.check_type(&option.inner, file, derive)
+            }
+            Type::Vector(vector) => {
+                derive == Derive::Default || self.check_type(&vector.inner, file, derive)
+            }
+            Type::Map(map) => match derive {
+                Derive::Default => true,
+                Derive::Eq => {
+                    self.check_type(&map.key, file, derive)
+                        && self.check_type(&map.value, file, derive)
+                }
+                Derive::Hash => false,
+            },
+            Type::Schema(schema_ref) => self.check_declaration(schema_ref, file, derive),
+            Type::Generic(generic) => {
+                self.check_declaration(&generic.schema, file, derive)
+                    && generic
+                        .args
+                        .iter()
+                        .all(|arg| self.check_type(arg, file, derive))
+            }
+        }
+    }
+
+    fn check_declaration(
+        &mut self,
+        schema_ref: &SchemaRef,
+        file: &ValidatedFile,
+        derive: Derive,
+    ) -> bool {
+        // Type parameters are bound by the derive itself, and unknown types already reported.
+        let Ok(resolved) = file.resolve(schema_ref) else {
+            return true;
+        };
+        let file = resolved.import.unwrap_or(file);
+        let name = schema_ref.name();
+
+        match (resolved.kind, derive) {
+            (TypeKind::Enum, Derive::Default) | (TypeKind::Union, Derive::Default) => false,
+            (TypeKind::Enum, _) => true,
+            (TypeKind::Schema, Derive::Default) => {
+                has_default(&file.schema_map[&SchemaRef::new(name)])
+            }
+            // Every declaration only needs to be checked once, including along cycles.
+            _ if !self
+                .visited
+                .insert((file.file.path.clone(), name.to_owned())) =>
+            {
+                true
+            }
+            (TypeKind::Schema, _) => {
+                let schema = &file.schema_map[&SchemaRef::new(name)];
+                schema
+                    .fields
+                    .iter()
+                    .all(|field| self.check_type(&field.ty, file, derive))
+            }
+            (TypeKind::Union, _) => file.union_map[name]
+                .variants
+                .iter()
+                .all(|variant| self.check_declaration(&variant.payload, file, derive)),
+        }
+    }
+}
+
+/// Whether a schema implements `Default`, either derived or generated from the defaults
+/// of its fields when every other field has an empty value.
+fn has_default(schema: &Schema) -> bool {
+    if requested_derives(&schema.annotations).any(|(_, derive)| derive == Derive::Default) {
+        return true;
+    }
+
+    let has_empty_value = |ty: &Type| match ty {
+        Type::Option(_) | Type::Vector(_) | Type::Map(_) => true,
+        Type::Primitive(primitive) => !matches!(
+            primitive,
+            PrimitiveType::Timestamp | PrimitiveType::Ulid | PrimitiveType::Uuid
+        ),
+        Type::Schema(_) | Type::Generic(_) => false,
+    };
+
+    schema.fields.iter().any(|field| field.default.is_some())
+        && schema
+            .fields
+            .iter()
+            .all(|field| field.default.is_some() || has_empty_value(&field.ty))
+}
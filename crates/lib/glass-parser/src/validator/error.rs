@@ -1,22 +1,211 @@
 use crate::ast::schema::SchemaRef;
+use crate::ast::span::Span;
+use crate::ast::types::Type;
 use thiserror::Error;
 
+/// Validation errors
+///
+/// Duplicate declarations carry the span of the offending declaration
+/// along with the span of the one it collides with.
 #[derive(Debug, Error)]
 pub enum ValidatorError {
-    #[error("A duplicate schema was found: `{0}`")]
-    DuplicateSchema(String),
+    #[error("A duplicate schema was found: `{name}`")]
+    DuplicateSchema {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
 
-    #[error("A duplicate interface was found: `{0}`")]
-    DuplicateInterface(String),
+    #[error("A duplicate enum was found: `{name}`")]
+    DuplicateEnum {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("A duplicate union was found: `{name}`")]
+    DuplicateUnion {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("A duplicate interface was found: `{name}`")]
+    DuplicateInterface {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("A duplicate constant was found: `{name}`")]
+    DuplicateConstant {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Value `{value}` of constant `{name}` {reason}")]
+    InvalidConstant {
+        name: String,
+        value: String,
+        reason: String,
+        span: Span,
+    },
 
     #[error("Schema `{schema}` contains a duplicate field: `{field}`")]
-    DuplicateField { schema: String, field: String },
+    DuplicateField {
+        schema: String,
+        field: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Enum `{enumeration}` contains a duplicate variant: `{variant}`")]
+    DuplicateVariant {
+        enumeration: String,
+        variant: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Union `{union}` contains a duplicate variant: `{variant}`")]
+    DuplicateUnionVariant {
+        union: String,
+        variant: String,
+        span: Span,
+        previous: Span,
+    },
 
     #[error("Interface `{interface}` contains a duplicate function: `{function}`")]
-    DuplicateFunction { interface: String, function: String },
+    DuplicateFunction {
+        interface: String,
+        function: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Schema `{schema}` uses field number {number} more than once")]
+    DuplicateFieldNumber {
+        schema: String,
+        number: u32,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Schema `{schema}` uses reserved field number {number}")]
+    ReservedFieldNumber {
+        schema: String,
+        number: u32,
+        span: Span,
+        reserved: Span,
+    },
+
+    #[error("Schema `{schema}` uses reserved field name `{field}`")]
+    ReservedFieldName {
+        schema: String,
+        field: String,
+        span: Span,
+        reserved: Span,
+    },
+
+    #[error("Field `{field}` of schema `{schema}` has no number, while other fields do")]
+    MissingFieldNumber {
+        schema: String,
+        field: String,
+        span: Span,
+    },
+
+    #[error("Default value `{value}` of field `{field}` in schema `{schema}` {reason}")]
+    InvalidDefault {
+        schema: String,
+        field: String,
+        value: String,
+        reason: String,
+        span: Span,
+    },
+
+    #[error("Annotation `@{name}` is used more than once")]
+    DuplicateAnnotation {
+        name: String,
+        span: Span,
+        previous: Span,
+    },
+
+    #[error("Annotation `@{name}` {reason}")]
+    InvalidAnnotation {
+        name: String,
+        reason: String,
+        span: Span,
+    },
 
-    #[error("A reference to an unknown schema was found: `{0:?}`")]
+    #[error("Function `{interface}.{function}` {reason}")]
+    InvalidThrows {
+        interface: String,
+        function: String,
+        reason: String,
+        span: Span,
+    },
+
+    #[error("Map keys must be a hashable primitive or an enum, found: `{key:?}`")]
+    InvalidMapKey { key: Type, span: Span },
+
+    #[error("Type parameter `{name}` of schema `{schema}` {reason}")]
+    InvalidTypeParameter {
+        schema: String,
+        name: String,
+        reason: String,
+        span: Span,
+    },
+
+    #[error("Type `{name}` expects {expected} type arguments, found {found}")]
+    InvalidTypeArguments {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+
+    #[error("Union `{name}` holds `{payload}`, which is not a schema")]
+    InvalidUnionPayload {
+        name: String,
+        payload: String,
+        span: Span,
+    },
+
+    #[error("A reference to an unknown schema was found: `{0}`")]
     SchemaNotFound(SchemaRef),
+
+    #[error("A reference to an unknown interface was found: `{0}`")]
+    InterfaceNotFound(SchemaRef),
+
+    #[error("Interface `{name}` extends itself")]
+    CyclicInterface { name: String, span: Span },
+
+    #[error(
+        "Function `{function}` of interface `{interface}` is declared by both `{first}` and `{second}`"
+    )]
+    ConflictingFunction {
+        interface: String,
+        function: String,
+        first: String,
+        second: String,
+        span: Span,
+    },
+
+    #[error("A reference is declared by more than one import: `{0}`")]
+    AmbiguousReference(SchemaRef),
+
+    #[error("Type `{name}` contains itself and can never be constructed")]
+    InfiniteType { name: String, span: Span },
 }
 
 pub type ValidatorResult<T> = Result<T, ValidatorError>;
+
+/// Validation warnings
+///
+/// Unlike errors, they never prevent a file from being validated.
+#[derive(Debug, Clone, Error)]
+pub enum ValidatorWarning {
+    #[error("Import `{path}` is never used")]
+    UnusedImport { path: String, span: Span },
+}
@@ -0,0 +1,154 @@
+use crate::ast::interface::{Function, Interface};
+use crate::validator::ValidatedFile;
+use crate::validator::error::ValidatorError;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tracing::error;
+
+/// Function served by an interface, either declared by it or inherited from
+/// one of the interfaces it extends.
+#[derive(Debug, Copy, Clone)]
+pub struct InheritedFunction<'a> {
+    pub function: &'a Function,
+    /// Interface declaring the function.
+    pub interface: &'a Interface,
+    /// File declaring the interface.
+    pub file: &'a ValidatedFile,
+}
+
+impl InheritedFunction<'_> {
+    /// Fully qualified name of the interface declaring the function.
+    pub fn origin(&self) -> String {
+        match &self.file.file.package {
+            Some(package) => package.qualify(&self.interface.name),
+            None => self.interface.name.clone(),
+        }
+    }
+}
+
+impl ValidatedFile {
+    /// Every function served by an interface of this file, the inherited ones first.
+    ///
+    /// Parents are visited in the order they are extended, and an interface reached
+    /// through more than one path only contributes its functions once.
+    pub fn interface_functions<'a>(
+        &'a self,
+        interface: &'a Interface,
+    ) -> Vec<InheritedFunction<'a>> {
+        let mut visited = HashSet::new();
+        let mut functions = Vec::new();
+        collect_functions(self, interface, &mut visited, &mut functions);
+        functions
+    }
+}
+
+fn collect_functions<'a>(
+    file: &'a ValidatedFile,
+    interface: &'a Interface,
+    visited: &mut HashSet<(PathBuf, String)>,
+    functions: &mut Vec<InheritedFunction<'a>>,
+) {
+    // Also guards against cycles, which are reported by the validator.
+    if !visited.insert((file.file.path.clone(), interface.name.clone())) {
+        return;
+    }
+
+    for parent_ref in &interface.extends {
+        if let Ok(parent) = file.resolve_interface(parent_ref) {
+            let parent_file = parent.import.unwrap_or(file);
+            collect_functions(parent_file, parent.interface, visited, functions);
+        }
+    }
+
+    functions.extend(
+        interface
+            .functions
+            .iter()
+            .map(|function| InheritedFunction {
+                function,
+                interface,
+                file,
+            }),
+    );
+}
+
+/// Checks the interfaces extended by the interfaces of the file.
+///
+/// Parents must exist and never lead back to the interface, and every function it
+/// serves must be declared once, so a name may not come from two different interfaces.
+pub(crate) fn validate_inheritance(
+    validated_file: &ValidatedFile,
+    errors: &mut Vec<ValidatorError>,
+    used_imports: &mut HashSet<PathBuf>,
+) {
+    for interface in &validated_file.file.interfaces {
+        for parent_ref in &interface.extends {
+            match validated_file.resolve_interface(parent_ref) {
+                Ok(parent) => {
+                    if let Some(import) = parent.import {
+                        used_imports.insert(import.file.path.clone());
+                    }
+                }
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if extends_itself(validated_file, interface) {
+            error!(interface_name = ?interface.name, "Cyclic interface inheritance detected");
+            errors.push(ValidatorError::CyclicInterface {
+                name: interface.name.clone(),
+                span: interface.name_span,
+            });
+            continue;
+        }
+
+        let mut origins = HashMap::new();
+        for inherited in validated_file.interface_functions(interface) {
+            let origin = inherited.origin();
+            let Some(first) = origins.insert(inherited.function.name.as_str(), origin.clone())
+            else {
+                continue;
+            };
+
+            // Conflicts with the interface's own functions point to them.
+            let span = match std::ptr::eq(inherited.interface, interface) {
+                true => inherited.function.span,
+                false => interface.name_span,
+            };
+            error!(interface_name = ?interface.name, function_name = ?inherited.function.name, "Conflicting inherited function detected");
+            errors.push(ValidatorError::ConflictingFunction {
+                interface: interface.name.clone(),
+                function: inherited.function.name.clone(),
+                first,
+                second: origin,
+                span,
+            });
+        }
+    }
+}
+
+/// Imports never form cycles, so only local parents can lead back to the interface.
+fn extends_itself(validated_file: &ValidatedFile, interface: &Interface) -> bool {
+    let mut visited = HashSet::new();
+    let mut pending = vec![interface];
+    while let Some(current) = pending.pop() {
+        for parent_ref in &current.extends {
+            let Ok(parent) = validated_file.resolve_interface(parent_ref) else {
+                continue;
+            };
+            if parent.import.is_some() {
+                continue;
+            }
+
+            if parent.interface.name == interface.name {
+                return true;
+            }
+
+            if visited.insert(parent.interface.name.as_str()) {
+                pending.push(parent.interface);
+            }
+        }
+    }
+
+    false
+}
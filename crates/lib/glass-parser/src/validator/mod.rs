@@ -1,88 +1,471 @@
+pub mod annotations;
+mod constraints;
+mod defaults;
+mod derives;
 pub mod error;
+pub mod inheritance;
+pub mod recursion;
+pub mod report;
 
-use crate::ast::interface::{FunctionParam, FunctionReturn, Interface};
-use crate::ast::schema::{Schema, SchemaRef};
-use crate::ast::types::Type;
+use crate::ast::constant::Constant;
+use crate::ast::enumeration::Enum;
+use crate::ast::interface::{Function, FunctionParam, FunctionReturn, Interface};
+use crate::ast::schema::{Reserved, Schema, SchemaRef};
+use crate::ast::span::Span;
+use crate::ast::types::{PrimitiveType, Type};
+use crate::ast::union::Union;
 use crate::prelude::*;
-use crate::validator::error::{ValidatorError, ValidatorResult};
+use crate::validator::error::{ValidatorError, ValidatorResult, ValidatorWarning};
+use crate::validator::recursion::Recursion;
+use crate::validator::report::ValidationReport;
 use std::collections::{HashMap, HashSet};
-use tracing::{error, info};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Errors a single function may declare, as status codes from 128 up are left to them.
+pub const MAX_DECLARED_ERRORS: usize = 128;
+
+/// Kind of named type a [SchemaRef] points to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TypeKind {
+    Schema,
+    Enum,
+    Union,
+}
+
+/// Outcome of resolving a [SchemaRef] from within a [ValidatedFile].
+#[derive(Debug, Copy, Clone)]
+pub struct ResolvedType<'a> {
+    pub kind: TypeKind,
+    /// The imported file declaring the type, or `None` if it is declared locally.
+    pub import: Option<&'a ValidatedFile>,
+}
+
+/// Outcome of resolving a reference to an interface from within a [ValidatedFile].
+#[derive(Debug, Copy, Clone)]
+pub struct ResolvedInterface<'a> {
+    pub interface: &'a Interface,
+    /// The imported file declaring the interface, or `None` if it is declared locally.
+    pub import: Option<&'a ValidatedFile>,
+}
 
 #[derive(Debug, Clone)]
 pub struct ValidatedFile {
     pub file: File,
+    /// Files brought into scope by this file's `import` statements.
+    pub imports: Vec<Arc<ValidatedFile>>,
+    pub constant_map: HashMap<String, Constant>,
     pub schema_map: HashMap<SchemaRef, Schema>,
+    pub enum_map: HashMap<String, Enum>,
+    pub union_map: HashMap<String, Union>,
     pub interface_map: HashMap<String, Interface>,
+    /// Cycles between the schemas and unions of this file.
+    pub recursion: Recursion,
+    /// Problems that did not prevent the file from being validated.
+    pub warnings: Vec<ValidatorWarning>,
 }
 
 impl ValidatedFile {
     #[tracing::instrument(skip_all, fields(path = ?file.path.to_str()))]
-    pub fn validate(file: File) -> ValidatorResult<Self> {
-        info!("Semantic validation has begun");
+    pub fn validate(file: File) -> Result<Self, ValidationReport> {
+        Self::validate_with_imports(file, vec![])
+    }
 
-        let schema_map = Self::build_schema_map(&file.schemas)?;
-        let interface_map = Self::build_interface_map(&file.interfaces)?;
+    /// Validates a file whose imports were already validated.
+    ///
+    /// Only the declarations of the given files are visible, imports are not transitive.
+    /// Validation carries on after an error whenever the rest of the file can still be
+    /// checked, so the report holds every problem found in a single run.
+    #[tracing::instrument(skip_all, fields(path = ?file.path.to_str()))]
+    pub fn validate_with_imports(
+        file: File,
+        imports: Vec<Arc<ValidatedFile>>,
+    ) -> Result<Self, ValidationReport> {
+        info!("Semantic validation has begun");
 
-        Self::validate_schema_ref(&file, &schema_map)?;
+        let mut errors = Vec::new();
+        let constant_map = Self::build_constant_map(&file.constants, &mut errors);
+        let schema_map = Self::build_schema_map(&file.schemas, &mut errors);
+        let enum_map = Self::build_enum_map(&file.enums, &schema_map, &mut errors);
+        let union_map = Self::build_union_map(&file.unions, &schema_map, &enum_map, &mut errors);
+        let interface_map = Self::build_interface_map(&file.interfaces, &mut errors);
+        annotations::validate_file_annotations(&file, &mut errors);
 
-        Ok(Self {
+        let mut validated_file = Self {
             file,
+            imports,
+            constant_map,
             schema_map,
+            enum_map,
+            union_map,
             interface_map,
-        })
+            recursion: Recursion::default(),
+            warnings: vec![],
+        };
+
+        let mut used_imports = HashSet::new();
+        validated_file.validate_schema_ref(&mut errors, &mut used_imports);
+        inheritance::validate_inheritance(&validated_file, &mut errors, &mut used_imports);
+        defaults::validate_field_defaults(&validated_file, &mut errors);
+        derives::validate_derives(&validated_file, &mut errors);
+
+        let (recursion, recursion_errors) = Recursion::analyze(&validated_file);
+        validated_file.recursion = recursion;
+        errors.extend(recursion_errors);
+        let warnings = validated_file.find_unused_imports(&used_imports);
+
+        if !errors.is_empty() {
+            error!(errors = errors.len(), "Semantic validation failed");
+            return Err(ValidationReport { errors, warnings });
+        }
+
+        validated_file.warnings = warnings;
+        Ok(validated_file)
     }
 
-    fn build_schema_map(schemas: &[Schema]) -> ValidatorResult<HashMap<SchemaRef, Schema>> {
-        let mut schema_map = HashMap::with_capacity(schemas.len());
+    /// Kind of the type declared by this file under `name`, ignoring imports.
+    pub fn declared_kind(&self, name: &str) -> Option<TypeKind> {
+        if self.schema_map.contains_key(&SchemaRef::new(name)) {
+            Some(TypeKind::Schema)
+        } else if self.enum_map.contains_key(name) {
+            Some(TypeKind::Enum)
+        } else if self.union_map.contains_key(name) {
+            Some(TypeKind::Union)
+        } else {
+            None
+        }
+    }
+
+    /// Resolves a reference to a named type.
+    ///
+    /// Unqualified references only see the file's own package, while qualified ones
+    /// must name the package of the declaring file. Local declarations shadow imported
+    /// ones, and a name declared by more than one import is rejected as ambiguous.
+    pub fn resolve(&self, schema_ref: &SchemaRef) -> ValidatorResult<ResolvedType<'_>> {
+        let (kind, import) = self.find_in_scope(
+            schema_ref,
+            |file| file.declared_kind(schema_ref.name()),
+            ValidatorError::SchemaNotFound,
+        )?;
+
+        Ok(ResolvedType { kind, import })
+    }
+
+    /// Resolves a reference to an interface, following the same rules as [ValidatedFile::resolve].
+    pub fn resolve_interface(
+        &self,
+        interface_ref: &SchemaRef,
+    ) -> ValidatorResult<ResolvedInterface<'_>> {
+        let (interface, import) = self.find_in_scope(
+            interface_ref,
+            |file| file.interface_map.get(interface_ref.name()),
+            ValidatorError::InterfaceNotFound,
+        )?;
+
+        Ok(ResolvedInterface { interface, import })
+    }
+
+    fn find_in_scope<'a, T>(
+        &'a self,
+        schema_ref: &SchemaRef,
+        declared: impl Fn(&'a ValidatedFile) -> Option<T>,
+        not_found: fn(SchemaRef) -> ValidatorError,
+    ) -> ValidatorResult<(T, Option<&'a ValidatedFile>)> {
+        let is_in_scope = |file: &ValidatedFile| {
+            let package = file.file.package.as_ref().map(ToString::to_string);
+            match schema_ref.package() {
+                Some(qualifier) => package.as_deref() == Some(qualifier),
+                None => package == self.file.package.as_ref().map(ToString::to_string),
+            }
+        };
+
+        if is_in_scope(self)
+            && let Some(declared) = declared(self)
+        {
+            return Ok((declared, None));
+        }
+
+        let mut candidates = self
+            .imports
+            .iter()
+            .filter(|import| is_in_scope(import))
+            .filter_map(|import| {
+                declared(import).map(|declared| (declared, Some(import.as_ref())))
+            });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(resolved), None) => Ok(resolved),
+            (Some(_), Some(_)) => {
+                error!(?schema_ref, "Reference matches more than one import");
+                Err(ValidatorError::AmbiguousReference(schema_ref.clone()))
+            }
+            (None, _) => {
+                error!(?schema_ref, "Reference to an undefined declaration");
+                Err(not_found(schema_ref.clone()))
+            }
+        }
+    }
+
+    /// Whether a reference held by the local type `from` closes a cycle, and so must be boxed.
+    pub fn is_recursive_ref(&self, from: &str, to: &SchemaRef) -> bool {
+        matches!(self.resolve(to), Ok(ResolvedType { import: None, .. }))
+            && self.recursion.is_cyclic(from, to.name())
+    }
+
+    /// Constants live in their own namespace, as their names are in screaming snake case.
+    fn build_constant_map(
+        constants: &[Constant],
+        errors: &mut Vec<ValidatorError>,
+    ) -> HashMap<String, Constant> {
+        let mut constant_map: HashMap<String, Constant> = HashMap::with_capacity(constants.len());
+        for constant in constants {
+            if let Some(previous) = constant_map.get(&constant.name) {
+                error!(constant_name = ?constant.name, "Duplicated constant detected");
+                errors.push(ValidatorError::DuplicateConstant {
+                    name: constant.name.clone(),
+                    span: constant.name_span,
+                    previous: previous.name_span,
+                });
+                continue;
+            }
+
+            if let Err(reason) = defaults::check_literal(&constant.ty, &constant.value) {
+                error!(constant_name = ?constant.name, "Invalid constant value");
+                errors.push(ValidatorError::InvalidConstant {
+                    name: constant.name.clone(),
+                    value: constant.value.to_string(),
+                    reason,
+                    span: constant.value_span,
+                });
+            }
+
+            constant_map.insert(constant.name.clone(), constant.clone());
+        }
+
+        constant_map
+    }
+
+    /// Duplicates are reported and skipped, so the first declaration is the one kept.
+    fn build_schema_map(
+        schemas: &[Schema],
+        errors: &mut Vec<ValidatorError>,
+    ) -> HashMap<SchemaRef, Schema> {
+        let mut schema_map: HashMap<SchemaRef, Schema> = HashMap::with_capacity(schemas.len());
         for schema in schemas {
-            if !schema_map
-                .keys()
-                .filter(|&key| key == &SchemaRef(schema.name.clone()))
-                .collect::<Vec<_>>()
-                .is_empty()
-            {
+            if let Some(previous) = schema_map.get(&SchemaRef::new(&schema.name)) {
                 error!(schema_name = ?schema.name, "Duplicated schema detected");
-                return Err(ValidatorError::DuplicateSchema(schema.name.clone()));
+                errors.push(ValidatorError::DuplicateSchema {
+                    name: schema.name.clone(),
+                    span: schema.name_span,
+                    previous: previous.name_span,
+                });
+                continue;
             }
 
-            let mut field_names = HashSet::new();
+            let mut field_spans = HashMap::new();
             for field in &schema.fields {
-                if !field_names.insert(field.name.clone()) {
+                if let Some(previous) = field_spans.insert(field.name.clone(), field.span) {
                     error!(schema_name = ?schema.name, field_name = ?field.name, "Duplicate field in schema detected");
-                    return Err(ValidatorError::DuplicateField {
+                    errors.push(ValidatorError::DuplicateField {
+                        schema: schema.name.clone(),
+                        field: field.name.clone(),
+                        span: field.span,
+                        previous,
+                    });
+                }
+            }
+
+            Self::validate_field_numbers(schema, errors);
+            schema_map.insert(SchemaRef::new(&schema.name), schema.clone());
+        }
+
+        schema_map
+    }
+
+    /// Field numbers are optional, but must then be given to every field of the schema.
+    fn validate_field_numbers(schema: &Schema, errors: &mut Vec<ValidatorError>) {
+        let reserved_number = |number: u32| {
+            schema.reserved.iter().find_map(|reserved| match reserved {
+                Reserved::Number {
+                    number: other,
+                    span,
+                } if *other == number => Some(*span),
+                _ => None,
+            })
+        };
+        let reserved_name = |name: &str| {
+            schema.reserved.iter().find_map(|reserved| match reserved {
+                Reserved::Name { name: other, span } if other == name => Some(*span),
+                _ => None,
+            })
+        };
+
+        let is_numbered = schema.fields.iter().any(|field| field.number.is_some());
+        let mut number_spans = HashMap::new();
+        for field in &schema.fields {
+            if let Some(reserved) = reserved_name(&field.name) {
+                error!(schema_name = ?schema.name, field_name = ?field.name, "Reserved field name used");
+                errors.push(ValidatorError::ReservedFieldName {
+                    schema: schema.name.clone(),
+                    field: field.name.clone(),
+                    span: field.span,
+                    reserved,
+                });
+            }
+
+            let Some(number) = field.number else {
+                if is_numbered {
+                    error!(schema_name = ?schema.name, field_name = ?field.name, "Field number missing");
+                    errors.push(ValidatorError::MissingFieldNumber {
                         schema: schema.name.clone(),
                         field: field.name.clone(),
+                        span: field.span,
                     });
                 }
+                continue;
+            };
+
+            if let Some(previous) = number_spans.insert(number, field.span) {
+                error!(schema_name = ?schema.name, number, "Duplicate field number detected");
+                errors.push(ValidatorError::DuplicateFieldNumber {
+                    schema: schema.name.clone(),
+                    number,
+                    span: field.span,
+                    previous,
+                });
+            }
+
+            if let Some(reserved) = reserved_number(number) {
+                error!(schema_name = ?schema.name, number, "Reserved field number used");
+                errors.push(ValidatorError::ReservedFieldNumber {
+                    schema: schema.name.clone(),
+                    number,
+                    span: field.span,
+                    reserved,
+                });
             }
-            schema_map.insert(SchemaRef(schema.name.clone()), schema.clone());
         }
+    }
 
-        Ok(schema_map)
+    fn build_enum_map(
+        enums: &[Enum],
+        schema_map: &HashMap<SchemaRef, Schema>,
+        errors: &mut Vec<ValidatorError>,
+    ) -> HashMap<String, Enum> {
+        let mut enum_map: HashMap<String, Enum> = HashMap::with_capacity(enums.len());
+        for enumeration in enums {
+            // Enums and schemas share the same namespace, as both are referenced the same way.
+            let previous = enum_map
+                .get(&enumeration.name)
+                .map(|previous| previous.name_span)
+                .or_else(|| {
+                    schema_map
+                        .get(&SchemaRef::new(&enumeration.name))
+                        .map(|previous| previous.name_span)
+                });
+            if let Some(previous) = previous {
+                error!(enum_name = ?enumeration.name, "Duplicated enum detected");
+                errors.push(ValidatorError::DuplicateEnum {
+                    name: enumeration.name.clone(),
+                    span: enumeration.name_span,
+                    previous,
+                });
+                continue;
+            }
+
+            let mut variant_spans = HashMap::new();
+            for variant in &enumeration.variants {
+                if let Some(previous) = variant_spans.insert(variant.name.clone(), variant.span) {
+                    error!(enum_name = ?enumeration.name, variant_name = ?variant.name, "Duplicate variant in enum detected");
+                    errors.push(ValidatorError::DuplicateVariant {
+                        enumeration: enumeration.name.clone(),
+                        variant: variant.name.clone(),
+                        span: variant.span,
+                        previous,
+                    });
+                }
+            }
+
+            enum_map.insert(enumeration.name.clone(), enumeration.clone());
+        }
+
+        enum_map
+    }
+
+    fn build_union_map(
+        unions: &[Union],
+        schema_map: &HashMap<SchemaRef, Schema>,
+        enum_map: &HashMap<String, Enum>,
+        errors: &mut Vec<ValidatorError>,
+    ) -> HashMap<String, Union> {
+        let mut union_map: HashMap<String, Union> = HashMap::with_capacity(unions.len());
+        for union in unions {
+            let previous = union_map
+                .get(&union.name)
+                .map(|previous| previous.name_span)
+                .or_else(|| enum_map.get(&union.name).map(|previous| previous.name_span))
+                .or_else(|| {
+                    schema_map
+                        .get(&SchemaRef::new(&union.name))
+                        .map(|previous| previous.name_span)
+                });
+            if let Some(previous) = previous {
+                error!(union_name = ?union.name, "Duplicated union detected");
+                errors.push(ValidatorError::DuplicateUnion {
+                    name: union.name.clone(),
+                    span: union.name_span,
+                    previous,
+                });
+                continue;
+            }
+
+            let mut variant_spans = HashMap::new();
+            for variant in &union.variants {
+                if let Some(previous) = variant_spans.insert(variant.name.clone(), variant.span) {
+                    error!(union_name = ?union.name, variant_name = ?variant.name, "Duplicate variant in union detected");
+                    errors.push(ValidatorError::DuplicateUnionVariant {
+                        union: union.name.clone(),
+                        variant: variant.name.clone(),
+                        span: variant.span,
+                        previous,
+                    });
+                }
+            }
+
+            union_map.insert(union.name.clone(), union.clone());
+        }
+
+        union_map
     }
 
     fn build_interface_map(
         interfaces: &[Interface],
-    ) -> ValidatorResult<HashMap<String, Interface>> {
-        let mut interface_map = HashMap::with_capacity(interfaces.len());
+        errors: &mut Vec<ValidatorError>,
+    ) -> HashMap<String, Interface> {
+        let mut interface_map: HashMap<String, Interface> =
+            HashMap::with_capacity(interfaces.len());
         for interface in interfaces {
-            if !interface_map
-                .keys()
-                .filter(|&key| key == &interface.name)
-                .collect::<Vec<_>>()
-                .is_empty()
-            {
+            if let Some(previous) = interface_map.get(&interface.name) {
                 error!(interface_name = ?interface.name, "Duplicated interface detected");
-                return Err(ValidatorError::DuplicateInterface(interface.name.clone()));
+                errors.push(ValidatorError::DuplicateInterface {
+                    name: interface.name.clone(),
+                    span: interface.name_span,
+                    previous: previous.name_span,
+                });
+                continue;
             }
 
-            let mut function_names = HashSet::new();
+            let mut function_spans = HashMap::new();
             for function in &interface.functions {
-                if !function_names.insert(function.name.clone()) {
+                if let Some(previous) = function_spans.insert(function.name.clone(), function.span)
+                {
                     error!(interface_name = ?interface.name, function_name = ?function.name, "Duplicate function in interface detected");
-                    return Err(ValidatorError::DuplicateFunction {
+                    errors.push(ValidatorError::DuplicateFunction {
                         interface: interface.name.clone(),
                         function: function.name.clone(),
+                        span: function.span,
+                        previous,
                     });
                 }
             }
@@ -90,73 +473,303 @@ impl ValidatedFile {
             interface_map.insert(interface.name.clone(), interface.clone());
         }
 
-        Ok(interface_map)
+        interface_map
     }
 
+    /// Checks every reference of the file, recording the imports they resolve to.
     fn validate_schema_ref(
-        file: &File,
-        schema_map: &HashMap<SchemaRef, Schema>,
-    ) -> ValidatorResult<()> {
-        for schema in &file.schemas {
+        &self,
+        errors: &mut Vec<ValidatorError>,
+        used_imports: &mut HashSet<PathBuf>,
+    ) {
+        for schema in &self.file.schemas {
+            self.validate_type_params(schema, errors);
             for field in &schema.fields {
-                Self::validate_type(&field.ty, schema_map)?;
+                self.validate_type(&field.ty, &schema.type_params, errors, used_imports);
             }
         }
 
-        for interface in &file.interfaces {
+        // Union payloads must be schemas, so that every variant encodes as a map on the wire.
+        for union in &self.file.unions {
+            for variant in &union.variants {
+                match self.resolve_recording(&variant.payload, used_imports) {
+                    Ok(resolved) if resolved.kind == TypeKind::Schema => {
+                        if let Err(error) = self.check_type_args(&variant.payload, resolved, 0) {
+                            errors.push(error);
+                        }
+                    }
+                    Ok(_) => {
+                        error!(union_name = ?union.name, payload = ?variant.payload, "Union payload is not a schema");
+                        errors.push(ValidatorError::InvalidUnionPayload {
+                            name: union.name.clone(),
+                            payload: variant.payload.to_string(),
+                            span: variant.payload.span,
+                        });
+                    }
+                    Err(error) => errors.push(error),
+                }
+            }
+        }
+
+        for interface in &self.file.interfaces {
             for function in &interface.functions {
-                Self::validate_function_param(&function.param, schema_map)?;
-                if let Some(return_type) = &function.return_type {
-                    Self::validate_function_return(return_type, schema_map)?;
+                match &function.param {
+                    FunctionParam::Stream(fn_type) | FunctionParam::Simple(fn_type) => {
+                        self.validate_type(fn_type, &[], errors, used_imports)
+                    }
+                    // Arguments are checked as the fields of the request schema.
+                    FunctionParam::Named(_) | FunctionParam::None => {}
+                }
+                match &function.return_type {
+                    Some(FunctionReturn::Stream(return_type))
+                    | Some(FunctionReturn::Simple(return_type)) => {
+                        self.validate_type(return_type, &[], errors, used_imports)
+                    }
+                    None => {}
                 }
+
+                self.validate_throws(&interface.name, function, errors, used_imports);
             }
         }
+    }
 
-        Ok(())
+    /// Declared errors are sent with their own status code, so they must be schemas
+    /// telling each other apart.
+    fn validate_throws(
+        &self,
+        interface: &str,
+        function: &Function,
+        errors: &mut Vec<ValidatorError>,
+        used_imports: &mut HashSet<PathBuf>,
+    ) {
+        let invalid_throws = |reason: String, span: Span| ValidatorError::InvalidThrows {
+            interface: interface.to_owned(),
+            function: function.name.clone(),
+            reason,
+            span,
+        };
+
+        if function.throws.len() > MAX_DECLARED_ERRORS {
+            error!(interface_name = ?interface, function_name = ?function.name, "Too many declared errors");
+            errors.push(invalid_throws(
+                format!("declares more than {MAX_DECLARED_ERRORS} errors"),
+                function.span,
+            ));
+        }
+
+        let mut declared = HashSet::new();
+        for error_ref in &function.throws {
+            if !declared.insert(error_ref.path.as_str()) {
+                errors.push(invalid_throws(
+                    format!("throws `{error_ref}` more than once"),
+                    error_ref.span,
+                ));
+                continue;
+            }
+
+            match self.resolve_recording(error_ref, used_imports) {
+                Ok(resolved) if resolved.kind == TypeKind::Schema => {
+                    if let Err(error) = self.check_type_args(error_ref, resolved, 0) {
+                        errors.push(error);
+                    }
+                }
+                Ok(_) => {
+                    error!(function_name = ?function.name, error = ?error_ref, "Declared error is not a schema");
+                    errors.push(invalid_throws(
+                        format!("throws `{error_ref}`, which is not a schema"),
+                        error_ref.span,
+                    ));
+                }
+                Err(error) => errors.push(error),
+            }
+        }
+    }
+
+    /// Type parameters must be unique, and may not share the name of a type in scope
+    /// so that every reference within the schema is unambiguous.
+    fn validate_type_params(&self, schema: &Schema, errors: &mut Vec<ValidatorError>) {
+        let invalid_param = |name: &str, reason: String| ValidatorError::InvalidTypeParameter {
+            schema: schema.name.clone(),
+            name: name.to_owned(),
+            reason,
+            span: schema.name_span,
+        };
+
+        let mut declared = HashSet::new();
+        for param in &schema.type_params {
+            if !declared.insert(param) {
+                error!(schema_name = ?schema.name, type_param = ?param, "Duplicate type parameter detected");
+                errors.push(invalid_param(
+                    param,
+                    "is declared more than once".to_owned(),
+                ));
+            } else if self.resolve(&SchemaRef::new(param)).is_ok() {
+                error!(schema_name = ?schema.name, type_param = ?param, "Type parameter shadows a type");
+                errors.push(invalid_param(param, format!("shadows the type `{param}`")));
+            }
+        }
     }
 
-    fn validate_type(ty: &Type, schema_map: &HashMap<SchemaRef, Schema>) -> ValidatorResult<()> {
+    /// Type parameters are only in scope within the fields of their schema.
+    fn validate_type(
+        &self,
+        ty: &Type,
+        type_params: &[String],
+        errors: &mut Vec<ValidatorError>,
+        used_imports: &mut HashSet<PathBuf>,
+    ) {
         match ty {
-            Type::Primitive(_) => Ok(()),
+            Type::Primitive(_) => {}
+            Type::Schema(schema_ref) if is_type_param(type_params, schema_ref) => {}
             Type::Schema(schema_ref) => {
-                if schema_map
-                    .keys()
-                    .filter(|&key| key == schema_ref)
-                    .collect::<Vec<_>>()
-                    .is_empty()
+                let checked = self
+                    .resolve_recording(schema_ref, used_imports)
+                    .and_then(|resolved| self.check_type_args(schema_ref, resolved, 0));
+                if let Err(error) = checked {
+                    errors.push(error);
+                }
+            }
+            Type::Generic(generic) => {
+                let checked = match is_type_param(type_params, &generic.schema) {
+                    true => Err(ValidatorError::InvalidTypeArguments {
+                        name: generic.schema.path.clone(),
+                        expected: 0,
+                        found: generic.args.len(),
+                        span: generic.schema.span,
+                    }),
+                    false => self
+                        .resolve_recording(&generic.schema, used_imports)
+                        .and_then(|resolved| {
+                            self.check_type_args(&generic.schema, resolved, generic.args.len())
+                        }),
+                };
+                if let Err(error) = checked {
+                    errors.push(error);
+                }
+
+                for arg in &generic.args {
+                    self.validate_type(arg, type_params, errors, used_imports);
+                }
+            }
+            Type::Option(option_type) => {
+                self.validate_type(&option_type.inner, type_params, errors, used_imports)
+            }
+            Type::Vector(vector_type) => {
+                self.validate_type(&vector_type.inner, type_params, errors, used_imports)
+            }
+            Type::Map(map_type) => {
+                if let Err(error) =
+                    self.validate_map_key(&map_type.key, map_type.span, type_params, used_imports)
                 {
-                    error!(?schema_ref, "Reference to an undefined schema defined");
-                    Err(ValidatorError::SchemaNotFound(schema_ref.clone()))
-                } else {
-                    Ok(())
+                    errors.push(error);
                 }
+                self.validate_type(&map_type.value, type_params, errors, used_imports);
             }
-            Type::Option(option_type) => Self::validate_type(&option_type.inner, schema_map),
-            Type::Vector(vector_type) => Self::validate_type(&vector_type.inner, schema_map),
         }
     }
 
-    fn validate_function_param(
-        param: &FunctionParam,
-        schema_map: &HashMap<SchemaRef, Schema>,
+    /// Map keys must be usable both in a `HashMap` and a `BTreeMap`, which rules out
+    /// floating point numbers, any composite type and type parameters, as they may
+    /// stand for any of those.
+    fn validate_map_key(
+        &self,
+        key: &Type,
+        span: Span,
+        type_params: &[String],
+        used_imports: &mut HashSet<PathBuf>,
     ) -> ValidatorResult<()> {
-        match param {
-            FunctionParam::Stream(fn_type) => Self::validate_type(fn_type, schema_map),
-            FunctionParam::Simple(fn_type) => Self::validate_type(fn_type, schema_map),
+        let is_valid_key = match key {
+            Type::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => false,
+            Type::Primitive(_) => true,
+            Type::Schema(schema_ref) if is_type_param(type_params, schema_ref) => false,
+            Type::Schema(schema_ref) => {
+                self.resolve_recording(schema_ref, used_imports)?.kind == TypeKind::Enum
+            }
+            Type::Option(_) | Type::Vector(_) | Type::Map(_) | Type::Generic(_) => false,
+        };
+
+        if !is_valid_key {
+            error!(?key, "Invalid map key type");
+            return Err(ValidatorError::InvalidMapKey {
+                key: key.clone(),
+                span: key.span().unwrap_or(span),
+            });
         }
+
+        Ok(())
     }
 
-    fn validate_function_return(
-        fn_return: &FunctionReturn,
-        schema_map: &HashMap<SchemaRef, Schema>,
+    /// Generic schemas take a type argument per type parameter, while any other named
+    /// type takes none.
+    fn check_type_args(
+        &self,
+        schema_ref: &SchemaRef,
+        resolved: ResolvedType<'_>,
+        found: usize,
     ) -> ValidatorResult<()> {
-        match fn_return {
-            FunctionReturn::Stream(return_type) => Self::validate_type(return_type, schema_map),
-            FunctionReturn::Simple(return_type) => Self::validate_type(return_type, schema_map),
+        let expected = match resolved.kind {
+            TypeKind::Schema => resolved
+                .import
+                .unwrap_or(self)
+                .schema_map
+                .get(&SchemaRef::new(schema_ref.name()))
+                .map_or(0, |schema| schema.type_params.len()),
+            TypeKind::Enum | TypeKind::Union => 0,
+        };
+
+        if expected != found {
+            error!(
+                ?schema_ref,
+                expected, found, "Invalid number of type arguments"
+            );
+            return Err(ValidatorError::InvalidTypeArguments {
+                name: schema_ref.path.clone(),
+                expected,
+                found,
+                span: schema_ref.span,
+            });
         }
+
+        Ok(())
+    }
+
+    /// Same as [ValidatedFile::resolve], also recording the import the reference resolved to.
+    fn resolve_recording(
+        &self,
+        schema_ref: &SchemaRef,
+        used_imports: &mut HashSet<PathBuf>,
+    ) -> ValidatorResult<ResolvedType<'_>> {
+        let resolved = self.resolve(schema_ref)?;
+        if let Some(import) = resolved.import {
+            used_imports.insert(import.file.path.clone());
+        }
+
+        Ok(resolved)
+    }
+
+    fn find_unused_imports(&self, used_imports: &HashSet<PathBuf>) -> Vec<ValidatorWarning> {
+        // Imports are validated in the order they are declared.
+        self.file
+            .imports
+            .iter()
+            .zip(&self.imports)
+            .filter(|(_, imported)| !used_imports.contains(&imported.file.path))
+            .map(|(import, _)| {
+                warn!(import = ?import.path, "Unused import detected");
+                ValidatorWarning::UnusedImport {
+                    path: import.path.clone(),
+                    span: import.span,
+                }
+            })
+            .collect()
     }
 }
 
+/// Whether the reference points to one of the given type parameters.
+fn is_type_param(type_params: &[String], schema_ref: &SchemaRef) -> bool {
+    schema_ref.package().is_none() && type_params.contains(&schema_ref.path)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -211,7 +824,140 @@ mod tests {
         file.try_parse().unwrap();
 
         let result = ValidatedFile::validate(file);
-        assert!(matches!(result, Err(ValidatorError::DuplicateSchema(_))));
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [ValidatorError::DuplicateSchema { .. }]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_named_params() {
+        let content = r#"
+            interface Accounts {
+                fn transfer(from: u64, from: u64, amount: Amount);
+            }
+
+            schema AccountsTransferRequest { id: u64; }
+        "#;
+        let (path, cleanup) = create_temp_file("named_params", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [
+                ValidatorError::DuplicateField { field, .. },
+                ValidatorError::DuplicateSchema { name, .. },
+                ValidatorError::SchemaNotFound(_),
+            ] if field == "from" && name == "AccountsTransferRequest"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_throws() {
+        let content = r#"
+            schema Locked { reason: string; }
+            enum Role { Admin; }
+
+            interface Accounts {
+                fn close(u64) throws Locked | Role | Locked | Missing;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("throws", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [
+                ValidatorError::InvalidThrows { reason: not_schema, .. },
+                ValidatorError::InvalidThrows { reason: duplicate, .. },
+                ValidatorError::SchemaNotFound(_),
+            ] if not_schema == "throws `Role`, which is not a schema"
+                && duplicate == "throws `Locked` more than once"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_interface_inheritance() {
+        let content = r#"
+            interface Health { fn ping(); }
+            interface Admin extends Health { fn reset(); }
+            interface Support extends Health { fn ticket(); }
+
+            // Health is reached twice, but only declares `ping` once.
+            interface Billing extends Admin, Support { fn charge(u64); }
+
+            interface Legacy extends Health { fn ping(); }
+            interface Cycle extends Loop { fn a(); }
+            interface Loop extends Cycle { fn b(); }
+            interface Broken extends Missing { fn c(); }
+        "#;
+        let (path, cleanup) = create_temp_file("interface_inheritance", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [
+                ValidatorError::ConflictingFunction { interface, first, second, .. },
+                ValidatorError::CyclicInterface { name: cycle, .. },
+                ValidatorError::CyclicInterface { name: inner, .. },
+                ValidatorError::InterfaceNotFound(_),
+            ] if interface == "Legacy" && first == "Health" && second == "Legacy"
+                && cycle == "Cycle" && inner == "Loop"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_generic_schemas() {
+        let content = r#"
+            enum Role { Admin; }
+            schema Page<T> { items: vec<T>; next: option<T> default none; }
+            schema Keyed<K> { values: map<K, u32>; }
+            schema Twice<T, T> { value: T; }
+            schema Shadow<Role> { value: Role; }
+            schema Defaulted<T> { value: T default 1; }
+
+            schema Uses {
+                nested: Page<Page<Role>>;
+                bare: Page;
+                extra: Page<Role, Role>;
+                role: Role<u32>;
+            }
+
+            union Payload { Paged(Page); }
+        "#;
+        let (path, cleanup) = create_temp_file("generic_schemas", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [
+                ValidatorError::InvalidMapKey { .. },
+                ValidatorError::InvalidTypeParameter { reason: duplicate, .. },
+                ValidatorError::InvalidTypeParameter { reason: shadows, .. },
+                ValidatorError::InvalidTypeArguments { expected: 1, found: 0, .. },
+                ValidatorError::InvalidTypeArguments { expected: 1, found: 2, .. },
+                ValidatorError::InvalidTypeArguments { name: role, expected: 0, found: 1, .. },
+                ValidatorError::InvalidTypeArguments { name: payload, expected: 1, found: 0, .. },
+                ValidatorError::InvalidDefault { schema, .. },
+            ] if duplicate == "is declared more than once" && shadows == "shadows the type `Role`"
+                && role == "Role" && payload == "Page" && schema == "Defaulted"
+        ));
 
         cleanup();
     }
@@ -227,7 +973,10 @@ mod tests {
         file.try_parse().unwrap();
 
         let result = ValidatedFile::validate(file);
-        assert!(matches!(result, Err(ValidatorError::DuplicateInterface(_))));
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [ValidatorError::DuplicateInterface { .. }]
+        ));
 
         cleanup();
     }
@@ -244,7 +993,562 @@ mod tests {
         file.try_parse().unwrap();
 
         let result = ValidatedFile::validate(file);
-        assert!(matches!(result, Err(ValidatorError::SchemaNotFound(_))));
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [ValidatorError::SchemaNotFound(_)]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_enum_reference() {
+        let content = r#"
+            enum Status { Active; Suspended; }
+
+            schema Account {
+                status: option<Status>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("enum_reference", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(result.is_ok());
+        assert!(result.unwrap().enum_map.contains_key("Status"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_duplicate_variant() {
+        let content = r#"
+            enum Status { Active; Active; }
+        "#;
+        let (path, cleanup) = create_temp_file("duplicate_variant", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [ValidatorError::DuplicateVariant { .. }]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_enum_schema_name_clash() {
+        let content = r#"
+            schema Status { id: u64; }
+            enum Status { Active; }
+        "#;
+        let (path, cleanup) = create_temp_file("enum_schema_clash", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [ValidatorError::DuplicateEnum { .. }]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_union_payload_not_schema() {
+        let content = r#"
+            enum Status { Active; }
+
+            union Event {
+                Changed(Status);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("union_payload", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let [
+            ValidatorError::InvalidUnionPayload {
+                name,
+                payload,
+                span,
+            },
+        ] = report.errors.as_slice()
+        else {
+            panic!("Expected an invalid union payload");
+        };
+        assert_eq!(name, "Event");
+        assert_eq!(payload, "Status");
+        assert_eq!(&content[span.start..span.end], "Status");
+        assert!(report.diagnostics()[0].hint.is_some());
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_union_reference() {
+        let content = r#"
+            schema UserCreated { id: u64; }
+
+            union Event {
+                Created(UserCreated);
+            }
+
+            interface Events {
+                fn subscribe(UserCreated) -> stream Event;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("union_reference", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(result.is_ok());
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_map_enum_key() {
+        let content = r#"
+            enum Region { Eu; Us; }
+
+            schema Counters {
+                per_region: map<Region, u64>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("map_enum_key", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(result.is_ok());
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_map_invalid_key() {
+        let content = r#"
+            schema Point { x: f64; }
+
+            schema Invalid {
+                by_float: map<f64, string>;
+                by_schema: map<Point, string>;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("map_invalid_key", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let result = ValidatedFile::validate(file);
+        assert!(matches!(
+            result.unwrap_err().errors.as_slice(),
+            [
+                ValidatorError::InvalidMapKey { .. },
+                ValidatorError::InvalidMapKey { .. }
+            ]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let content = r#"
+            schema User { id: u64; id: string; }
+            schema User { name: string; }
+
+            enum Status { Active; }
+
+            schema Account {
+                owner: Owner;
+                counters: map<f64, Missing>;
+            }
+
+            union Event {
+                Changed(Status);
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("collect_errors", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                ValidatorError::DuplicateField { .. },
+                ValidatorError::DuplicateSchema { .. },
+                ValidatorError::SchemaNotFound(_),
+                ValidatorError::InvalidMapKey { .. },
+                ValidatorError::SchemaNotFound(_),
+                ValidatorError::InvalidUnionPayload { .. },
+            ]
+        ));
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.diagnostics().len(), 6);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_recursive_types() {
+        let content = r#"
+            schema Node { next: option<Node>; children: vec<Node>; }
+
+            schema Literal { value: i64; }
+            schema Binary { lhs: Expr; rhs: Expr; }
+            union Expr { Lit(Literal); Add(Binary); }
+
+            schema Leaf { parent: option<Node>; }
+        "#;
+        let (path, cleanup) = create_temp_file("recursive_types", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let validated_file = ValidatedFile::validate(file).unwrap();
+        assert!(validated_file.recursion.is_cyclic("Node", "Node"));
+        assert!(validated_file.recursion.is_cyclic("Binary", "Expr"));
+        assert!(!validated_file.recursion.is_cyclic("Expr", "Literal"));
+        assert!(!validated_file.recursion.is_cyclic("Leaf", "Node"));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_infinite_types() {
+        let content = r#"
+            schema Node { next: Node; }
+
+            schema Binary { lhs: Expr; }
+            union Expr { Add(Binary); }
+
+            schema Holder { node: Node; }
+        "#;
+        let (path, cleanup) = create_temp_file("infinite_types", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let names = report
+            .errors
+            .iter()
+            .map(|error| match error {
+                ValidatorError::InfiniteType { name, .. } => name.as_str(),
+                _ => panic!("Expected an infinite type error"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Binary", "Expr", "Node"]);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_field_numbers() {
+        let content = r#"
+            schema User {
+                reserved 3;
+                reserved "email";
+
+                id: u64 = 1;
+                name: string = 1;
+                age: u8 = 3;
+                email: string = 4;
+                nickname: string;
+            }
+
+            schema Unnumbered { id: u64; }
+        "#;
+        let (path, cleanup) = create_temp_file("field_numbers", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                ValidatorError::DuplicateFieldNumber { number: 1, .. },
+                ValidatorError::ReservedFieldNumber { number: 3, .. },
+                ValidatorError::ReservedFieldName { .. },
+                ValidatorError::MissingFieldNumber { .. },
+            ]
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_constants() {
+        let content = r#"
+            const MAX_PAGE_SIZE: u32 = 500;
+            const MAX_PAGE_SIZE: u32 = 100;
+            const SMALL: u8 = 256;
+            const OFFSET: i16 = -32768;
+            const HUGE: u128 = 340282366920938463463374607431768211455;
+            const BIG: i128 = 170141183460469231731687303715884105728;
+            const NAME: string = 42;
+            const ID: uuid = "0";
+        "#;
+        let (path, cleanup) = create_temp_file("constants", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                ValidatorError::DuplicateConstant { .. },
+                ValidatorError::InvalidConstant { name: small, .. },
+                ValidatorError::InvalidConstant { name: big, .. },
+                ValidatorError::InvalidConstant { name: string, .. },
+                ValidatorError::InvalidConstant { name: uuid, .. },
+            ] if small == "SMALL" && big == "BIG" && string == "NAME" && uuid == "ID"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_field_defaults() {
+        let content = r#"
+            enum Role { Guest; Admin; }
+
+            schema User { id: u64; }
+
+            schema Config {
+                retries: u8 default 3;
+                limit: u128 default 340282366920938463463374607431768211455;
+                nickname: option<string> default none;
+                ratio: f64 default 1;
+                role: Role default Admin;
+                overflow: u8 default 256;
+                name: string default 3;
+                required: string default none;
+                tags: vec<string> default "a";
+                owner: User default none;
+                unknown: Role default Owner;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("field_defaults", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let fields = report
+            .errors
+            .iter()
+            .map(|error| match error {
+                ValidatorError::InvalidDefault { field, .. } => field.as_str(),
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec!["overflow", "name", "required", "tags", "owner", "unknown"]
+        );
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_field_constraints() {
+        let content = r#"
+            schema User {
+                name: string @len(1..64) @pattern("^[a-z]+$");
+                nickname: option<string> @non_empty;
+                age: u8 @range(0..=150);
+                ratio: f64 @range(0..1);
+                tags: vec<string> @len(..10);
+                id: u64 @len(1..2);
+                level: u8 @range(0..300);
+                offset: i32 @range(0.5..);
+                code: string @pattern("[a-");
+                count: u32 @range(10..1);
+                flags: bytes @range(..);
+            }
+
+            @non_empty
+            enum Role { Admin; }
+        "#;
+        let (path, cleanup) = create_temp_file("field_constraints", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let names = report
+            .errors
+            .iter()
+            .map(|error| match error {
+                ValidatorError::InvalidAnnotation { name, .. } => name.as_str(),
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "len",
+                "range",
+                "range",
+                "pattern",
+                "range",
+                "range",
+                "non_empty"
+            ]
+        );
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_annotations() {
+        let content = r#"
+            @idempotent
+            schema User {
+                @deprecated
+                @deprecated
+                id: u64;
+            }
+
+            @rust(derive = 42)
+            enum Role { Admin; }
+
+            interface Users {
+                @timeout("soon")
+                @retry(3)
+                fn get(User) -> User;
+            }
+        "#;
+        let (path, cleanup) = create_temp_file("annotations", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        // Unknown annotations such as `@retry` are left to codegen plugins.
+        let report = ValidatedFile::validate(file).unwrap_err();
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                ValidatorError::InvalidAnnotation { name: idempotent, .. },
+                ValidatorError::DuplicateAnnotation { .. },
+                ValidatorError::InvalidAnnotation { name: rust, .. },
+                ValidatorError::InvalidAnnotation { name: timeout, .. },
+            ] if idempotent == "idempotent" && rust == "rust" && timeout == "timeout"
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_default_derive() {
+        let content = r#"
+            @rust(derive = "Default")
+            schema User { id: u64; }
+
+            @rust(derive = "Default")
+            enum Role { Admin; }
+
+            @rust(derive = "std::default::Default")
+            union Owner { User(User); }
+
+            @rust(derive = "Clone, Default")
+            schema Settings { retries: u32 default 3; }
+        "#;
+        let (path, cleanup) = create_temp_file("default_derive", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                ValidatorError::InvalidAnnotation { reason, .. },
+                ValidatorError::InvalidAnnotation { .. },
+                ValidatorError::InvalidAnnotation { .. },
+            ] if reason.contains("field defaults")
+        ));
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_field_derives() {
+        let content = r#"
+            enum Role { Admin; }
+            schema Point { x: f64; }
+            union Owner { Point(Point); }
+
+            @rust(derive = "Default")
+            schema Member { role: Role; }
+
+            @rust(derive = "Eq")
+            schema Shape { points: vec<Point>; }
+
+            @rust(derive = "std::hash::Hash")
+            schema Labels { labels: map<string, string>; }
+
+            @rust(derive = "Hash")
+            union Holder { Point(Point); }
+
+            @rust(derive = "Default, Eq, Hash")
+            schema Account { id: u64; role: option<Role>; owners: vec<Member>; }
+        "#;
+        let (path, cleanup) = create_temp_file("field_derives", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let reasons = report
+            .errors
+            .iter()
+            .map(|error| match error {
+                ValidatorError::InvalidAnnotation { reason, span, .. } => {
+                    assert_eq!(&content[span.start..span.start + 5], "@rust");
+                    reason.as_str()
+                }
+                error => panic!("Unexpected error: {error:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            vec![
+                "can't derive `Default`, which field `role` doesn't implement",
+                "can't derive `Eq`, which field `points` doesn't implement",
+                "can't derive `Hash`, which field `labels` doesn't implement",
+                "can't derive `Hash`, which variant `Point` doesn't implement",
+            ]
+        );
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_error_spans() {
+        let content = "schema User { id: u64; }\nschema User { id: u64; }\n";
+        let (path, cleanup) = create_temp_file("error_spans", content);
+        let mut file = File::try_new(path).unwrap();
+        file.try_parse().unwrap();
+
+        let report = ValidatedFile::validate(file).unwrap_err();
+        let [ValidatorError::DuplicateSchema { span, previous, .. }] = *report.errors.as_slice()
+        else {
+            panic!("Expected a duplicate schema error");
+        };
+        assert_eq!(&content[span.start..span.end], "User");
+        assert_eq!(span.start, 32);
+        assert_eq!(previous.start, 7);
+
+        let diagnostic = Diagnostic::from(&ValidatorError::DuplicateSchema {
+            name: "User".to_owned(),
+            span,
+            previous,
+        });
+        assert_eq!(diagnostic.labels.len(), 2);
+        assert!(diagnostic.hint.is_some());
 
         cleanup();
     }
@@ -0,0 +1,287 @@
+use crate::ast::schema::SchemaRef;
+use crate::ast::span::Span;
+use crate::ast::types::Type;
+use crate::validator::error::ValidatorError;
+use crate::validator::{ResolvedType, TypeKind, ValidatedFile};
+use std::collections::{HashMap, HashSet};
+use tracing::error;
+
+/// Recursion analysis of the schemas and unions declared by a file.
+///
+/// Both hold their fields inline, so a type reaching itself without going through
+/// a vector or a map is infinitely sized unless the cycle is broken by a `Box`.
+/// Types are grouped in strongly connected components of the containment graph,
+/// and every reference between two types of the same component must be boxed.
+///
+/// Imports never form cycles, so only local types can be recursive.
+#[derive(Debug, Clone, Default)]
+pub struct Recursion {
+    /// Component of every type that is part of a cycle.
+    components: HashMap<String, usize>,
+}
+
+impl Recursion {
+    /// Whether both types belong to the same cycle.
+    pub fn is_cyclic(&self, from: &str, to: &str) -> bool {
+        match (self.components.get(from), self.components.get(to)) {
+            (Some(from), Some(to)) => from == to,
+            _ => false,
+        }
+    }
+
+    /// Builds the containment graph of the file and finds its cycles.
+    ///
+    /// Cycles whose types can never be constructed, because every path around them
+    /// goes through required fields, are reported as errors.
+    pub(crate) fn analyze(validated_file: &ValidatedFile) -> (Self, Vec<ValidatorError>) {
+        let graph = Graph::build(validated_file);
+
+        let mut tarjan = Tarjan::new(&graph);
+        for node in 0..graph.nodes.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+
+        let mut components = HashMap::new();
+        for (index, component) in tarjan.components.iter().enumerate() {
+            let is_cyclic = component.len() > 1
+                || graph.nodes[component[0]]
+                    .edges
+                    .iter()
+                    .any(|edge| edge.to == Some(component[0]));
+            if is_cyclic {
+                for &node in component {
+                    components.insert(graph.nodes[node].name.clone(), index);
+                }
+            }
+        }
+
+        let constructible = graph.constructible();
+        let mut errors = Vec::new();
+        for (node, data) in graph.nodes.iter().enumerate() {
+            // Types that only depend on an infinite cycle are reported through the cycle itself.
+            if !constructible.contains(&node) && components.contains_key(&data.name) {
+                error!(type_name = ?data.name, "Infinitely recursive type detected");
+                errors.push(ValidatorError::InfiniteType {
+                    name: data.name.clone(),
+                    span: data.name_span,
+                });
+            }
+        }
+
+        (Self { components }, errors)
+    }
+}
+
+/// Reference held inline by a schema field or a union variant.
+struct Edge {
+    /// The referenced local type, or `None` for anything declared elsewhere.
+    to: Option<usize>,
+    /// Whether a value of the referencing type must hold a value of the referenced one.
+    required: bool,
+}
+
+struct Node {
+    name: String,
+    name_span: Span,
+    kind: TypeKind,
+    edges: Vec<Edge>,
+}
+
+struct Graph {
+    nodes: Vec<Node>,
+}
+
+impl Graph {
+    fn build(validated_file: &ValidatedFile) -> Self {
+        // Sorting keeps the reported errors in a stable order.
+        let mut nodes = validated_file
+            .schema_map
+            .values()
+            .map(|schema| (schema.name.clone(), schema.name_span, TypeKind::Schema))
+            .chain(
+                validated_file
+                    .union_map
+                    .values()
+                    .map(|union| (union.name.clone(), union.name_span, TypeKind::Union)),
+            )
+            .map(|(name, name_span, kind)| Node {
+                name,
+                name_span,
+                kind,
+                edges: vec![],
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.name.clone(), index))
+            .collect::<HashMap<_, _>>();
+
+        for node in &mut nodes {
+            let mut refs = Vec::new();
+            match node.kind {
+                TypeKind::Schema => {
+                    let schema = &validated_file.schema_map[&SchemaRef::new(&node.name)];
+                    for field in &schema.fields {
+                        Self::collect_inline_refs(&field.ty, true, &mut refs);
+                    }
+                }
+                TypeKind::Union => {
+                    for variant in &validated_file.union_map[&node.name].variants {
+                        refs.push((&variant.payload, true));
+                    }
+                }
+                TypeKind::Enum => {}
+            }
+
+            node.edges = refs
+                .into_iter()
+                .map(|(schema_ref, required)| {
+                    let to = match validated_file.resolve(schema_ref) {
+                        Ok(ResolvedType { import: None, .. }) => {
+                            indices.get(schema_ref.name()).copied()
+                        }
+                        _ => None,
+                    };
+
+                    Edge { to, required }
+                })
+                .collect();
+        }
+
+        Self { nodes }
+    }
+
+    /// Options hold their value inline, while vectors and maps live on the heap
+    /// and are never part of a cycle.
+    ///
+    /// Type arguments may or may not be held inline by the generic schema, so they
+    /// are conservatively taken as optional inline references.
+    fn collect_inline_refs<'t>(
+        ty: &'t Type,
+        required: bool,
+        refs: &mut Vec<(&'t SchemaRef, bool)>,
+    ) {
+        match ty {
+            Type::Schema(schema_ref) => refs.push((schema_ref, required)),
+            Type::Generic(generic) => {
+                refs.push((&generic.schema, required));
+                for arg in &generic.args {
+                    Self::collect_inline_refs(arg, false, refs);
+                }
+            }
+            Type::Option(option_type) => Self::collect_inline_refs(&option_type.inner, false, refs),
+            Type::Primitive(_) | Type::Vector(_) | Type::Map(_) => {}
+        }
+    }
+
+    /// Types for which a finite value exists.
+    ///
+    /// A schema needs every required field to be constructible, while a union only needs
+    /// one of its variants to be.
+    fn constructible(&self) -> HashSet<usize> {
+        let is_constructible = |edge: &Edge, constructible: &HashSet<usize>| match edge.to {
+            Some(to) => constructible.contains(&to),
+            None => true,
+        };
+
+        let mut constructible = HashSet::new();
+        loop {
+            let mut changed = false;
+            for (index, node) in self.nodes.iter().enumerate() {
+                if constructible.contains(&index) {
+                    continue;
+                }
+
+                let is_node_constructible = match node.kind {
+                    TypeKind::Union if !node.edges.is_empty() => node
+                        .edges
+                        .iter()
+                        .any(|edge| is_constructible(edge, &constructible)),
+                    _ => node
+                        .edges
+                        .iter()
+                        .filter(|edge| edge.required)
+                        .all(|edge| is_constructible(edge, &constructible)),
+                };
+
+                if is_node_constructible {
+                    constructible.insert(index);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return constructible;
+            }
+        }
+    }
+}
+
+/// Tarjan's strongly connected components algorithm.
+struct Tarjan<'g> {
+    graph: &'g Graph,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'g> Tarjan<'g> {
+    fn new(graph: &'g Graph) -> Self {
+        let len = graph.nodes.len();
+        Self {
+            graph,
+            index: 0,
+            indices: vec![None; len],
+            low_links: vec![0; len],
+            on_stack: vec![false; len],
+            stack: vec![],
+            components: vec![],
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.low_links[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for edge in &self.graph.nodes[node].edges {
+            let Some(to) = edge.to else {
+                continue;
+            };
+
+            match self.indices[to] {
+                None => {
+                    self.visit(to);
+                    self.low_links[node] = self.low_links[node].min(self.low_links[to]);
+                }
+                Some(index) if self.on_stack[to] => {
+                    self.low_links[node] = self.low_links[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low_links[node]) == self.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+
+            self.components.push(component);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::diagnostic::Diagnostic;
+use crate::validator::error::{ValidatorError, ValidatorWarning};
+use std::fmt::{Display, Formatter};
+
+/// Every problem found while validating a file that failed validation.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidatorError>,
+    pub warnings: Vec<ValidatorWarning>,
+}
+
+impl ValidationReport {
+    /// Diagnostics for every error, followed by every warning.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.errors
+            .iter()
+            .map(Diagnostic::from)
+            .chain(self.warnings.iter().map(Diagnostic::from))
+            .collect()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} error(s) and {} warning(s)",
+            self.errors.len(),
+            self.warnings.len()
+        )
+    }
+}
@@ -0,0 +1,18 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+pub struct ChannelConfig {
+    pub http: ChannelHttpConfig,
+    pub security: ChannelSecurityConfig,
+}
+
+pub struct ChannelHttpConfig {
+    pub server_address: SocketAddr,
+    /// Name the certificate of the server is issued for, e.g. `localhost`.
+    pub server_name: String,
+}
+
+pub struct ChannelSecurityConfig {
+    /// Certificate the server is trusted with, usually the one it serves.
+    pub tls_certificate: PathBuf,
+}
@@ -0,0 +1,56 @@
+use crate::message::status::Status;
+use crate::message::{DecodeError, EncodeError};
+use crate::security::error::SecurityError;
+use crate::validation::ValidationErrors;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Failed to encode a message: {0}")]
+    Encoding(EncodeError),
+
+    #[error("Failed to decode a message: {0}")]
+    Decoding(DecodeError),
+
+    #[error("Failed with status: {0:#?}")]
+    Status(Status),
+
+    /// The request violated the constraints of its schema, answered with [Status::BadRequest].
+    #[error("Invalid request: {0}")]
+    InvalidRequest(ValidationErrors),
+
+    #[error("The call ended without a response")]
+    NoResponse,
+
+    #[error("Channel error: {0}")]
+    Channel(String),
+
+    #[error("Security error: {0}")]
+    Security(#[from] SecurityError),
+
+    #[error("Failed to connect: {0}")]
+    Connect(#[from] quinn::ConnectError),
+
+    #[error("Connection error: {0}")]
+    Connection(#[from] quinn::ConnectionError),
+
+    #[error("Failed to read from a stream: {0}")]
+    Read(#[from] quinn::ReadExactError),
+
+    #[error("Failed to write to a stream: {0}")]
+    Write(#[from] quinn::WriteError),
+
+    #[error("IO error: {0}")]
+    StdIo(#[from] std::io::Error),
+}
+
+/// Failure of a call, either reported by the service or by the client itself.
+#[derive(Debug, Error)]
+pub enum CallError<E> {
+    /// Error the service answered with, declared by the function or raised by its implementation.
+    #[error("The service answered with an error")]
+    Service(E),
+
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
@@ -0,0 +1,65 @@
+use crate::client::error::ClientError;
+use crate::client::{Channel, RequestStream, ResponseStream};
+use crate::server::handler::TypedHandler;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Messages sent ahead of their receiver, in either direction of a call.
+const BUFFER: usize = 32;
+
+/// Channel handing calls to a [Handler](crate::server::handler::Handler) of the same
+/// process, e.g. to test a service without going through the network.
+///
+/// Like a server, it hands the requests of a call to the handler as they are sent,
+/// so calls must be opened within a Tokio runtime.
+#[derive(Clone)]
+pub struct LocalChannel {
+    handler: TypedHandler,
+}
+
+impl LocalChannel {
+    pub fn new(handler: TypedHandler) -> Self {
+        Self { handler }
+    }
+}
+
+#[async_trait]
+impl Channel for LocalChannel {
+    async fn open(&self, mut requests: RequestStream) -> Result<ResponseStream, ClientError> {
+        let (request_sender, request_receiver) = mpsc::channel(BUFFER);
+        let (response_sender, response_receiver) = mpsc::channel(BUFFER);
+
+        // A request failing to be encoded ends the call, and is answered with its error.
+        let failures = response_sender.clone();
+        tokio::spawn(async move {
+            while let Some(request) = requests.next().await {
+                match request {
+                    Ok(request) => {
+                        if request_sender.send(request).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        let _ = failures.send(Err(error)).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        let handler = self.handler.clone();
+        tokio::spawn(async move {
+            let requests = Box::pin(ReceiverStream::new(request_receiver));
+            let mut responses = handler.handle_call(requests);
+            while let Some(response) = responses.next().await {
+                if response_sender.send(Ok(response)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(response_receiver)))
+    }
+}
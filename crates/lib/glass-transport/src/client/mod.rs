@@ -0,0 +1,221 @@
+use crate::client::error::{CallError, ClientError};
+use crate::message::status::Status;
+use crate::message::{DecodeError, Message, decode_payload, encode_payload};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt};
+
+pub mod config;
+pub mod error;
+pub mod local;
+pub mod webtransport;
+
+/// Requests sent over a call.
+///
+/// A request failing to be encoded ends the call, and is answered with its error.
+pub type RequestStream = Pin<Box<dyn Stream<Item = Result<Message, ClientError>> + Send>>;
+
+/// Responses answered over a call.
+pub type ResponseStream = Pin<Box<dyn Stream<Item = Result<Message, ClientError>> + Send>>;
+
+/// Values decoded from the responses of a streaming call.
+pub type CallStream<T, E> = Pin<Box<dyn Stream<Item = Result<T, CallError<E>>> + Send>>;
+
+/// Decodes the error a service answered with from the status and payload of a response,
+/// or returns `None` when the status is not one of its errors.
+pub type ErrorDecoder<E> = fn(Status, &[u8]) -> Option<Result<E, DecodeError>>;
+
+/// Carries the calls of a [Client] to a service.
+///
+/// Every call is an exchange of its own, unary ones sending and answering a single message.
+#[async_trait]
+pub trait Channel: Send + Sync {
+    /// Opens a call, sending the requests and yielding the responses as they come.
+    async fn open(&self, requests: RequestStream) -> Result<ResponseStream, ClientError>;
+}
+
+/// Calls the functions of services through a [Channel].
+///
+/// Requests are routed by the service and function they name, while responses carry
+/// either the value returned by the function, or the error matching their status.
+#[derive(Clone)]
+pub struct Client {
+    channel: Arc<dyn Channel>,
+}
+
+impl Client {
+    pub fn new(channel: impl Channel + 'static) -> Self {
+        Self {
+            channel: Arc::new(channel),
+        }
+    }
+
+    /// Calls a function taking and returning a single value.
+    ///
+    /// Functions taking no parameter are sent an empty payload.
+    pub async fn unary<T, E>(
+        &self,
+        service: &str,
+        function: &str,
+        payload: Vec<u8>,
+        decode_error: ErrorDecoder<E>,
+    ) -> Result<T, CallError<E>>
+    where
+        T: DeserializeOwned,
+    {
+        let request = Message::request(new_call_id(), service, function, payload);
+        let responses = self
+            .channel
+            .open(Box::pin(tokio_stream::once(Ok(request))))
+            .await?;
+
+        first_response(responses, decode_error).await
+    }
+
+    /// Calls a function returning a stream of values.
+    pub async fn server_streaming<T, E>(
+        &self,
+        service: &str,
+        function: &str,
+        payload: Vec<u8>,
+        decode_error: ErrorDecoder<E>,
+    ) -> Result<CallStream<T, E>, CallError<E>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        E: Send + 'static,
+    {
+        let request = Message::request(new_call_id(), service, function, payload);
+        let responses = self
+            .channel
+            .open(Box::pin(tokio_stream::once(Ok(request))))
+            .await?;
+
+        Ok(decode_stream(responses, decode_error))
+    }
+
+    /// Calls a function taking a stream of values and returning a single one.
+    pub async fn client_streaming<R, T, E>(
+        &self,
+        service: &str,
+        function: &str,
+        requests: impl Stream<Item = R> + Send + 'static,
+        decode_error: ErrorDecoder<E>,
+    ) -> Result<T, CallError<E>>
+    where
+        R: Serialize,
+        T: DeserializeOwned,
+    {
+        let requests = encode_stream(service, function, requests);
+        let responses = self.channel.open(requests).await?;
+
+        first_response(responses, decode_error).await
+    }
+
+    /// Calls a function taking and returning streams of values.
+    pub async fn bidirectional_streaming<R, T, E>(
+        &self,
+        service: &str,
+        function: &str,
+        requests: impl Stream<Item = R> + Send + 'static,
+        decode_error: ErrorDecoder<E>,
+    ) -> Result<CallStream<T, E>, CallError<E>>
+    where
+        R: Serialize,
+        T: DeserializeOwned + Send + 'static,
+        E: Send + 'static,
+    {
+        let requests = encode_stream(service, function, requests);
+        let responses = self.channel.open(requests).await?;
+
+        Ok(decode_stream(responses, decode_error))
+    }
+}
+
+/// Decodes the error of a service from the payload of an [Status::Internal] response.
+///
+/// Used by functions declaring no errors, whose failures can only come from the service.
+pub fn decode_service_error<E: DeserializeOwned>(
+    status: Status,
+    payload: &[u8],
+) -> Option<Result<E, DecodeError>> {
+    (status == Status::Internal && !payload.is_empty()).then(|| decode_payload(payload))
+}
+
+/// Messages of a call share its id, so that they can be told apart from other calls.
+fn new_call_id() -> u128 {
+    ulid::Ulid::new().0
+}
+
+fn encode_stream<R: Serialize>(
+    service: &str,
+    function: &str,
+    requests: impl Stream<Item = R> + Send + 'static,
+) -> RequestStream {
+    let id = new_call_id();
+    let service = service.to_owned();
+    let function = function.to_owned();
+
+    let mut failed = false;
+    Box::pin(requests.map_while(move |request| {
+        if failed {
+            return None;
+        }
+
+        match encode_payload(&request) {
+            Ok(payload) => Some(Ok(Message::request(id, &service, &function, payload))),
+            Err(error) => {
+                failed = true;
+                Some(Err(ClientError::Encoding(error)))
+            }
+        }
+    }))
+}
+
+async fn first_response<T, E>(
+    mut responses: ResponseStream,
+    decode_error: ErrorDecoder<E>,
+) -> Result<T, CallError<E>>
+where
+    T: DeserializeOwned,
+{
+    match responses.next().await {
+        Some(response) => decode_response(response?, decode_error),
+        None => Err(ClientError::NoResponse.into()),
+    }
+}
+
+fn decode_stream<T, E>(responses: ResponseStream, decode_error: ErrorDecoder<E>) -> CallStream<T, E>
+where
+    T: DeserializeOwned + Send + 'static,
+    E: Send + 'static,
+{
+    Box::pin(responses.map(move |response| decode_response(response?, decode_error)))
+}
+
+fn decode_response<T, E>(
+    response: Message,
+    decode_error: ErrorDecoder<E>,
+) -> Result<T, CallError<E>>
+where
+    T: DeserializeOwned,
+{
+    match response.status {
+        Status::Success => {
+            decode_payload(&response.payload).map_err(|error| ClientError::Decoding(error).into())
+        }
+        status => match decode_error(status, &response.payload) {
+            Some(Ok(error)) => Err(CallError::Service(error)),
+            Some(Err(error)) => Err(ClientError::Decoding(error).into()),
+            None if status == Status::BadRequest && !response.payload.is_empty() => {
+                match decode_payload(&response.payload) {
+                    Ok(errors) => Err(ClientError::InvalidRequest(errors).into()),
+                    Err(error) => Err(ClientError::Decoding(error).into()),
+                }
+            }
+            None => Err(ClientError::Status(status).into()),
+        },
+    }
+}
@@ -0,0 +1,282 @@
+use crate::client::config::{ChannelConfig, ChannelHttpConfig};
+use crate::client::error::ClientError;
+use crate::client::{Channel, RequestStream, ResponseStream};
+use crate::message::{Message, decode_payload, encode_payload};
+use crate::security::error::SecurityError;
+use crate::security::tls::TlsStore;
+use async_trait::async_trait;
+use quinn::crypto::rustls::QuicClientConfig;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Responses read ahead of the caller before the stream of a call stops being read.
+const RESPONSE_BUFFER: usize = 32;
+
+const CONTROL_STREAM: u64 = 0x00;
+const HEADERS_FRAME: u64 = 0x01;
+const SETTINGS_FRAME: u64 = 0x04;
+const WEBTRANSPORT_STREAM: u64 = 0x41;
+
+/// Settings telling the server that the client speaks WebTransport, namely
+/// `SETTINGS_ENABLE_CONNECT_PROTOCOL`, `SETTINGS_H3_DATAGRAM` and
+/// `SETTINGS_ENABLE_WEBTRANSPORT`.
+const SETTINGS: [(u64, u64); 3] = [(0x08, 1), (0x33, 1), (0x2b60_3742, 1)];
+
+/// Index of `:status 200` in the static table of QPACK.
+const STATUS_OK: u8 = 25;
+
+/// Channel carrying calls to a [Server](crate::server::Server) over WebTransport.
+///
+/// A single session is opened when connecting, each call then going over a bidirectional
+/// stream of its own. Messages are framed as the server reads them, each CBOR encoded
+/// message following its length as a big endian `u64`.
+pub struct WebTransportChannel {
+    connection: quinn::Connection,
+    session_id: u64,
+    /// Streams ending the session once closed, kept along with the endpoint driving them.
+    _control: quinn::SendStream,
+    _session: (quinn::SendStream, quinn::RecvStream),
+    _endpoint: quinn::Endpoint,
+}
+
+impl WebTransportChannel {
+    /// Connects to the server and opens a WebTransport session, trusting the certificate
+    /// of the configuration.
+    pub async fn connect(config: &ChannelConfig) -> Result<Self, ClientError> {
+        let certificate = TlsStore::try_load_certificate(&config.security.tls_certificate).await?;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(certificate).map_err(SecurityError::Rustls)?;
+
+        let mut tls_config = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+
+        let quic_client_config =
+            QuicClientConfig::try_from(tls_config).map_err(SecurityError::CipherSuite)?;
+
+        let server_address = config.http.server_address;
+        let bind_address: SocketAddr = match server_address {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+
+        let mut endpoint = quinn::Endpoint::client(bind_address)?;
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(quic_client_config)));
+
+        let connection = endpoint
+            .connect(server_address, &config.http.server_name)?
+            .await?;
+
+        // The server only accepts the session once it knows the client supports WebTransport,
+        // which the settings sent over the control stream tell it.
+        let mut settings = Vec::new();
+        for (id, value) in SETTINGS {
+            write_varint(&mut settings, id);
+            write_varint(&mut settings, value);
+        }
+
+        let mut buffer = Vec::new();
+        write_varint(&mut buffer, CONTROL_STREAM);
+        write_frame(&mut buffer, SETTINGS_FRAME, &settings);
+
+        let mut control = connection.open_uni().await?;
+        control.write_all(&buffer).await?;
+
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, HEADERS_FRAME, &connect_headers(&config.http));
+
+        let (mut send, mut recv) = connection.open_bi().await?;
+        send.write_all(&buffer).await?;
+
+        if !read_connect_response(&mut recv).await? {
+            return Err(ClientError::Channel(
+                "The server refused the WebTransport session".to_owned(),
+            ));
+        }
+
+        // Sessions are identified by the stream of the request opening them.
+        let session_id = u64::from(send.id());
+
+        Ok(Self {
+            connection,
+            session_id,
+            _control: control,
+            _session: (send, recv),
+            _endpoint: endpoint,
+        })
+    }
+}
+
+#[async_trait]
+impl Channel for WebTransportChannel {
+    async fn open(&self, mut requests: RequestStream) -> Result<ResponseStream, ClientError> {
+        let (mut send, mut recv) = self.connection.open_bi().await?;
+
+        let mut header = Vec::new();
+        write_varint(&mut header, WEBTRANSPORT_STREAM);
+        write_varint(&mut header, self.session_id);
+        send.write_all(&header).await?;
+
+        let (response_sender, response_receiver) = mpsc::channel(RESPONSE_BUFFER);
+
+        // A request failing to be encoded or sent ends the call, and is answered with its error.
+        let failures = response_sender.clone();
+        tokio::spawn(async move {
+            while let Some(request) = requests.next().await {
+                let written = match request {
+                    Ok(request) => write_message(&request, &mut send).await,
+                    Err(error) => Err(error),
+                };
+
+                if let Err(error) = written {
+                    let _ = failures.send(Err(error)).await;
+                    return;
+                }
+            }
+
+            // Finishing the stream ends the requests of the call on the server.
+            let _ = send.finish();
+        });
+
+        tokio::spawn(async move {
+            loop {
+                let response = match read_message(&mut recv).await {
+                    Ok(Some(response)) => Ok(response),
+                    Ok(None) => break,
+                    Err(error) => Err(error),
+                };
+
+                let failed = response.is_err();
+                if response_sender.send(response).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(response_receiver)))
+    }
+}
+
+/// Extended CONNECT request opening the session, as a QPACK field section referring to
+/// no table.
+fn connect_headers(http: &ChannelHttpConfig) -> Vec<u8> {
+    let authority = format!("{}:{}", http.server_name, http.server_address.port());
+    let fields = [
+        (":method", "CONNECT"),
+        (":protocol", "webtransport"),
+        (":scheme", "https"),
+        (":authority", authority.as_str()),
+        (":path", "/"),
+    ];
+
+    // Required insert count and base, both zero without a dynamic table.
+    let mut block = vec![0, 0];
+    for (name, value) in fields {
+        // Literal field line with a literal name, none of them Huffman encoded.
+        write_prefixed_int(&mut block, 0x20, 3, name.len());
+        block.extend_from_slice(name.as_bytes());
+        write_prefixed_int(&mut block, 0x00, 7, value.len());
+        block.extend_from_slice(value.as_bytes());
+    }
+
+    block
+}
+
+/// Reads the response to the CONNECT request, telling whether the session was accepted.
+async fn read_connect_response(recv: &mut quinn::RecvStream) -> Result<bool, ClientError> {
+    loop {
+        let frame_type = read_varint(recv).await?;
+        let length = read_varint(recv).await?;
+        let mut payload = vec![0; length as usize];
+        recv.read_exact(&mut payload).await?;
+
+        // Other frames, such as the reserved ones sent as grease, are skipped.
+        if frame_type != HEADERS_FRAME {
+            continue;
+        }
+
+        // The status comes first, indexed in the static table when it is a common one.
+        return Ok(payload.get(2) == Some(&(0xc0 | STATUS_OK)));
+    }
+}
+
+async fn write_message(message: &Message, send: &mut quinn::SendStream) -> Result<(), ClientError> {
+    let payload = encode_payload(message).map_err(ClientError::Encoding)?;
+
+    let mut buffer = Vec::with_capacity(8 + payload.len());
+    buffer.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    buffer.extend_from_slice(&payload);
+    send.write_all(&buffer).await?;
+
+    Ok(())
+}
+
+/// Reads the next message of a call, or `None` once the server finished its stream.
+async fn read_message(recv: &mut quinn::RecvStream) -> Result<Option<Message>, ClientError> {
+    let mut length = [0; 8];
+    match recv.read_exact(&mut length).await {
+        Ok(()) => (),
+        Err(quinn::ReadExactError::FinishedEarly(0)) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    }
+
+    let mut buffer = vec![0; u64::from_be_bytes(length) as usize];
+    recv.read_exact(&mut buffer).await?;
+
+    decode_payload(&buffer)
+        .map(Some)
+        .map_err(ClientError::Decoding)
+}
+
+fn write_frame(buffer: &mut Vec<u8>, frame_type: u64, payload: &[u8]) {
+    write_varint(buffer, frame_type);
+    write_varint(buffer, payload.len() as u64);
+    buffer.extend_from_slice(payload);
+}
+
+/// Writes a variable length integer of QUIC, whose two high bits tell its length.
+fn write_varint(buffer: &mut Vec<u8>, value: u64) {
+    match value {
+        0..0x40 => buffer.push(value as u8),
+        0x40..0x4000 => buffer.extend_from_slice(&(value as u16 | 0x4000).to_be_bytes()),
+        0x4000..0x4000_0000 => {
+            buffer.extend_from_slice(&(value as u32 | 0x8000_0000).to_be_bytes())
+        }
+        _ => buffer.extend_from_slice(&(value | 0xc000_0000_0000_0000).to_be_bytes()),
+    }
+}
+
+async fn read_varint(recv: &mut quinn::RecvStream) -> Result<u64, ClientError> {
+    let mut bytes = [0; 8];
+    recv.read_exact(&mut bytes[..1]).await?;
+
+    let length = 1 << (bytes[0] >> 6);
+    recv.read_exact(&mut bytes[1..length]).await?;
+    bytes[0] &= 0x3f;
+
+    Ok(bytes[..length]
+        .iter()
+        .fold(0, |value, byte| value << 8 | u64::from(*byte)))
+}
+
+/// Writes an integer of QPACK, sharing its first byte with the given flags.
+fn write_prefixed_int(buffer: &mut Vec<u8>, flags: u8, prefix_bits: u32, value: usize) {
+    let max = (1 << prefix_bits) - 1;
+    if value < max {
+        buffer.push(flags | value as u8);
+        return;
+    }
+
+    buffer.push(flags | max as u8);
+    let mut rest = value - max;
+    while rest >= 0x80 {
+        buffer.push(rest as u8 | 0x80);
+        rest >>= 7;
+    }
+    buffer.push(rest as u8);
+}
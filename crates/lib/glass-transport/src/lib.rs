@@ -1,3 +1,9 @@
+pub mod client;
 pub mod message;
 pub mod security;
 pub mod server;
+pub mod validation;
+
+/// Streams taken and returned by streaming functions, re-exported so generated code only
+/// depends on this crate to name them.
+pub use futures::stream::Stream;
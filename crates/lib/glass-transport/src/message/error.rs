@@ -0,0 +1,19 @@
+use crate::message::status::Status;
+use crate::message::{DecodeError, EncodeError};
+
+/// Errors declared by a function with `throws`.
+///
+/// Each declared error is sent with its own [Status::Custom] code, starting at
+/// [Status::FIRST_DECLARED] in declaration order, along with its CBOR encoded schema
+/// as payload. Failures of the service itself are sent with [Status::Internal].
+pub trait DeclaredError: Sized {
+    /// Status sent along with the error.
+    fn status(&self) -> Status;
+
+    /// Encodes the payload of the error.
+    fn encode(&self) -> Result<Vec<u8>, EncodeError>;
+
+    /// Decodes an error from the status and payload of a response, or `None` when
+    /// the status is not one of the function's errors.
+    fn decode(status: Status, payload: &[u8]) -> Option<Result<Self, DecodeError>>;
+}
@@ -1,20 +1,92 @@
+use crate::message::status::Status;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod error;
 pub mod status;
 pub mod types;
 
+/// Metadata key holding the fully qualified name of the service a request is sent to,
+/// as in [ControlMessage::service].
+pub const SERVICE_METADATA: &str = "glass-service";
+
+/// Metadata key holding the name of the function a request calls.
+pub const FUNCTION_METADATA: &str = "glass-function";
+
+pub type EncodeError = ciborium::ser::Error<std::io::Error>;
+pub type DecodeError = ciborium::de::Error<std::io::Error>;
+
+/// Encodes a value as a CBOR payload.
+pub fn encode_payload<T: Serialize>(value: &T) -> Result<Vec<u8>, EncodeError> {
+    let mut payload = Vec::new();
+    ciborium::ser::into_writer(value, &mut payload)?;
+    Ok(payload)
+}
+
+/// Decodes a value from a CBOR payload.
+pub fn decode_payload<T: DeserializeOwned>(payload: &[u8]) -> Result<T, DecodeError> {
+    ciborium::de::from_reader(payload)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub id: u128,
     pub message_type: types::MessageType,
+    /// Outcome of the call, always [Status::Success] for requests.
+    pub status: Status,
     pub metadata: HashMap<String, String>,
+    /// CBOR encoded parameter of the function, left empty for functions taking none.
+    ///
+    /// Responses hold the CBOR encoded value returned by the function, or the error
+    /// matching their status.
     pub payload: Vec<u8>,
 }
 
+impl Message {
+    /// Request calling a function of a service, routed through its metadata.
+    pub fn request(id: u128, service: &str, function: &str, payload: Vec<u8>) -> Self {
+        let metadata = HashMap::from([
+            (SERVICE_METADATA.to_owned(), service.to_owned()),
+            (FUNCTION_METADATA.to_owned(), function.to_owned()),
+        ]);
+
+        Self {
+            id,
+            message_type: types::MessageType::DataStream,
+            status: Status::Success,
+            metadata,
+            payload,
+        }
+    }
+
+    /// Response to a request, sharing its id and metadata.
+    pub fn response(request: &Message, status: Status, payload: Vec<u8>) -> Self {
+        Self {
+            id: request.id,
+            message_type: request.message_type,
+            status,
+            metadata: request.metadata.clone(),
+            payload,
+        }
+    }
+
+    /// Service the message is routed to.
+    pub fn service(&self) -> Option<&str> {
+        self.metadata.get(SERVICE_METADATA).map(String::as_str)
+    }
+
+    /// Function the message is routed to.
+    pub fn function(&self) -> Option<&str> {
+        self.metadata.get(FUNCTION_METADATA).map(String::as_str)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlMessage {
-    operation: types::ControlOperationType,
-    service: String,
-    function: String,
+    pub operation: types::ControlOperationType,
+    /// Fully qualified service name, prefixed by its package when it declares one
+    /// (e.g. `acme.billing.v1.Billing`).
+    pub service: String,
+    pub function: String,
 }
@@ -17,11 +17,25 @@ pub enum Status {
     Custom(u8),
 }
 
+impl Status {
+    /// Code of the first error declared by a function, the following ones taking the next codes.
+    pub const FIRST_DECLARED: u8 = 128;
+}
+
 impl From<Status> for u8 {
     fn from(status: Status) -> Self {
         match status {
+            Status::Success => 0,
+            Status::Internal => 1,
+            Status::Protocol => 2,
+            Status::Unknown => 3,
+
+            Status::NoSuchService => 10,
+            Status::NoSuchMethod => 11,
+
+            Status::BadRequest => 20,
+
             Status::Custom(custom) => custom,
-            _ => status.into(),
         }
     }
 }
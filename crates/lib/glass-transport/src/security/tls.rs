@@ -9,11 +9,7 @@ impl TlsStore {
         certificate_path: &Path,
         key_path: &Path,
     ) -> Result<(CertificateDer<'a>, PrivateKeyDer<'a>), SecurityError> {
-        if !certificate_path.exists() {
-            return Err(SecurityError::CertificateNotFound(
-                certificate_path.to_string_lossy().to_string(),
-            ));
-        }
+        let certificate = Self::try_load_certificate(certificate_path).await?;
 
         if !key_path.exists() {
             return Err(SecurityError::KeyNotFound(
@@ -21,10 +17,8 @@ impl TlsStore {
             ));
         }
 
-        let certificate_data = tokio::fs::read(certificate_path).await?;
         let key_data = tokio::fs::read(key_path).await?;
 
-        let certificate = CertificateDer::from(certificate_data);
         let key = match PrivateKeyDer::try_from(key_data) {
             Ok(key) => key,
             Err(error) => {
@@ -34,4 +28,19 @@ impl TlsStore {
 
         Ok((certificate, key))
     }
+
+    /// Loads a certificate on its own, e.g. to trust the server it is served by.
+    pub async fn try_load_certificate<'a>(
+        certificate_path: &Path,
+    ) -> Result<CertificateDer<'a>, SecurityError> {
+        if !certificate_path.exists() {
+            return Err(SecurityError::CertificateNotFound(
+                certificate_path.to_string_lossy().to_string(),
+            ));
+        }
+
+        let certificate_data = tokio::fs::read(certificate_path).await?;
+
+        Ok(CertificateDer::from(certificate_data))
+    }
 }
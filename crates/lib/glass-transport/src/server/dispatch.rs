@@ -0,0 +1,222 @@
+//! Building blocks of the servers generated for interfaces, which decode the requests
+//! of each function, call its implementation and answer with what it returned.
+
+use crate::message::error::DeclaredError;
+use crate::message::status::Status;
+use crate::message::{EncodeError, Message, decode_payload, encode_payload};
+use crate::server::error::ServerError;
+use crate::server::handler::{Handler, RequestStream, ResponseStream, handle_requests};
+use crate::validation::Validate;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Values taken by a function with a `stream` parameter, decoded from the requests of a call.
+pub type Requests<T> = Pin<Box<dyn Stream<Item = T> + Send + Sync>>;
+
+/// Encodes the error a function failed with, along with the status it is sent with.
+pub type ErrorEncoder<E> = fn(&E) -> Result<(Status, Vec<u8>), EncodeError>;
+
+/// Encodes a failure of a service as the payload of an [Status::Internal] response.
+///
+/// Used by functions declaring no errors, whose failures can only come from the service.
+pub fn encode_service_error<E: Serialize>(error: &E) -> Result<(Status, Vec<u8>), EncodeError> {
+    Ok((Status::Internal, encode_payload(error)?))
+}
+
+/// Encodes an error declared by a function with the status of its declaration.
+pub fn encode_declared_error<E: DeclaredError>(
+    error: &E,
+) -> Result<(Status, Vec<u8>), EncodeError> {
+    Ok((error.status(), error.encode()?))
+}
+
+/// Decodes the parameter of a function from the payload of a request.
+pub fn decode_request<T: DeserializeOwned>(request: &Message) -> Result<T, ServerError> {
+    decode_payload(&request.payload).map_err(ServerError::Decoding)
+}
+
+/// Decodes and validates the values streamed by the requests of a call, starting with
+/// its first request.
+///
+/// The first request failing to be decoded or validated fails the call. Any later one ends
+/// the stream instead, and the call is then answered with its failure through the returned
+/// [StreamFailure].
+pub fn decode_stream<T>(
+    request: &Message,
+    requests: RequestStream,
+) -> Result<(Requests<T>, StreamFailure), ServerError>
+where
+    T: DeserializeOwned + Validate + Send + Sync + 'static,
+{
+    let first = decode_valid_request(request)?;
+    let failure = StreamFailure {
+        header: Message::response(request, Status::Success, vec![]),
+        error: Arc::default(),
+    };
+
+    let failed_with = failure.error.clone();
+    let rest = requests.scan((), move |_, request| {
+        future::ready(match decode_valid_request(&request) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                debug!(?error, "Failed to decode a streamed request");
+                *failed_with.lock().unwrap() = Some(error);
+                None
+            }
+        })
+    });
+
+    Ok((Box::pin(stream::once(async { first }).chain(rest)), failure))
+}
+
+fn decode_valid_request<T>(request: &Message) -> Result<T, ServerError>
+where
+    T: DeserializeOwned + Validate,
+{
+    let value: T = decode_request(request)?;
+    value.validate()?;
+    Ok(value)
+}
+
+/// Failure of a request streamed to a function after the first one, which answers the call
+/// in place of the function.
+pub struct StreamFailure {
+    header: Message,
+    error: Arc<Mutex<Option<ServerError>>>,
+}
+
+impl StreamFailure {
+    /// Answers the call with the responses of the function until a streamed request fails,
+    /// ending the call with the failure of the request instead.
+    pub fn answer<'a>(self, responses: ResponseStream<'a>) -> ResponseStream<'a> {
+        let responses = responses.map(Some).chain(stream::once(async { None }));
+
+        Box::pin(responses.scan(false, move |failed, response| {
+            if *failed {
+                return future::ready(None);
+            }
+
+            match self.error.lock().unwrap().take() {
+                Some(error) => {
+                    *failed = true;
+                    future::ready(Some(failure(&self.header, error)))
+                }
+                None => future::ready(response),
+            }
+        }))
+    }
+}
+
+/// Answers a request with the value a function returned, or the error it failed with.
+pub fn respond<T, E>(
+    request: &Message,
+    result: Result<T, E>,
+    encode_error: ErrorEncoder<E>,
+) -> Result<Message, ServerError>
+where
+    T: Serialize,
+{
+    let encoded = match result {
+        Ok(value) => encode_payload(&value).map(|payload| (Status::Success, payload)),
+        Err(error) => encode_error(&error),
+    };
+    let (status, payload) = encoded.map_err(ServerError::Encoding)?;
+
+    Ok(Message::response(request, status, payload))
+}
+
+/// Answers a request with the values a function streamed, or the error it failed with.
+///
+/// A value failing to be encoded ends the call, answered with [Status::Internal].
+pub fn respond_stream<'a, T, E>(
+    request: Message,
+    result: Result<impl Stream<Item = T> + Send + 'a, E>,
+    encode_error: ErrorEncoder<E>,
+) -> ResponseStream<'a>
+where
+    T: Serialize + 'a,
+{
+    let values = match result {
+        Ok(values) => values,
+        Err(error) => {
+            let response = match encode_error(&error) {
+                Ok((status, payload)) => Message::response(&request, status, payload),
+                Err(error) => failure(&request, ServerError::Encoding(error)),
+            };
+            return single_response(response);
+        }
+    };
+
+    Box::pin(values.scan(false, move |failed, value| {
+        if *failed {
+            return future::ready(None);
+        }
+
+        let response = match encode_payload(&value) {
+            Ok(payload) => Message::response(&request, Status::Success, payload),
+            Err(error) => {
+                *failed = true;
+                failure(&request, ServerError::Encoding(error))
+            }
+        };
+        future::ready(Some(response))
+    }))
+}
+
+/// Answers a call with a single response.
+pub fn single_response<'a>(response: Message) -> ResponseStream<'a> {
+    Box::pin(stream::once(async { response }))
+}
+
+/// Answers a call according to the function named by its first request.
+///
+/// Calls to one of the `streaming` functions are answered by `answer`, given their first
+/// request and the following ones, and fail with the status of its error. Requests to any
+/// other function are handled one at a time through [Handler::handle].
+pub fn handle_streaming<'a, H, F, A>(
+    handler: &'a H,
+    requests: RequestStream,
+    streaming: &'a [&'a str],
+    answer: A,
+) -> ResponseStream<'a>
+where
+    H: Handler + ?Sized,
+    A: FnOnce(Message, RequestStream) -> F + Send + 'a,
+    F: Future<Output = Result<ResponseStream<'a>, ServerError>> + Send + 'a,
+{
+    let responses = async move {
+        let (request, requests) = requests.into_future().await;
+        let Some(request) = request else {
+            return stream::empty().boxed();
+        };
+
+        let is_streaming = request
+            .function()
+            .is_some_and(|function| streaming.contains(&function));
+        if !is_streaming {
+            let requests = Box::pin(stream::once(async { request }).chain(requests));
+            return handle_requests(handler, requests);
+        }
+
+        // The request is handed over, so its id and metadata are kept to answer failures.
+        let header = Message::response(&request, Status::Success, vec![]);
+        match answer(request, requests).await {
+            Ok(responses) => responses,
+            Err(error) => {
+                debug!(?error, "Failed to handle a call");
+                single_response(failure(&header, error))
+            }
+        }
+    };
+
+    Box::pin(stream::once(responses).flatten())
+}
+
+fn failure(request: &Message, error: ServerError) -> Message {
+    Message::response(request, error.status(), error.payload())
+}
@@ -1,5 +1,7 @@
+use crate::message::encode_payload;
 use crate::message::status::Status;
 use crate::security::error::SecurityError;
+use crate::validation::ValidationErrors;
 use h3::error::StreamError;
 use thiserror::Error;
 
@@ -23,9 +25,33 @@ pub enum ServerError {
     #[error("Failed with status: {0:#?}")]
     Status(Status),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(#[from] ValidationErrors),
+
     #[error("H3 stream error: {0}")]
     Stream(#[from] StreamError),
 
     #[error("IO error: {0}")]
     StdIo(#[from] std::io::Error),
 }
+
+impl ServerError {
+    /// Status reported to the peer for this error.
+    pub fn status(&self) -> Status {
+        match self {
+            ServerError::Status(status) => *status,
+            ServerError::InvalidRequest(_) => Status::BadRequest,
+            ServerError::Decoding(_) => Status::Protocol,
+            _ => Status::Internal,
+        }
+    }
+
+    /// Payload reported to the peer along with the status, holding the violations of an
+    /// invalid request, and nothing for any other error.
+    pub fn payload(&self) -> Vec<u8> {
+        match self {
+            ServerError::InvalidRequest(errors) => encode_payload(errors).unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+}
@@ -2,6 +2,8 @@ use crate::message::Message;
 use crate::message::types::MessageType;
 use crate::server::error::ServerError;
 use async_trait::async_trait;
+use futures::StreamExt;
+use futures::stream::{BoxStream, Stream};
 use h3::ext::Protocol;
 use h3::quic;
 use h3::server::Connection;
@@ -9,18 +11,64 @@ use h3_webtransport::server::AcceptedBi::BidiStream;
 use h3_webtransport::server::WebTransportSession;
 use h3_webtransport::stream::{RecvStream, SendStream};
 use http::Method;
+use std::io::ErrorKind;
+use std::pin::Pin;
 use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::bytes::Bytes;
 use tracing::{debug, error, info};
 
+/// Requests read ahead of the handler before the stream of a call stops being read, which
+/// holds back the client until the handler catches up.
+const REQUEST_BUFFER: usize = 32;
+
+/// Requests received over a call, in the order they were sent.
+pub type RequestStream = Pin<Box<dyn Stream<Item = Message> + Send + Sync>>;
+
+/// Responses answered over a call, which ends once they are exhausted.
+pub type ResponseStream<'a> = BoxStream<'a, Message>;
+
 #[async_trait]
-pub trait Handler {
+pub trait Handler: Send + Sync {
     async fn handle(&self, message: Message) -> Result<Message, ServerError>;
+
+    /// Handles the requests sent over a call, answering them as they come.
+    ///
+    /// Requests are handled one at a time through [Handler::handle] by default, which
+    /// handlers of streaming functions override to take and answer streams of messages.
+    fn handle_call(&self, requests: RequestStream) -> ResponseStream<'_> {
+        handle_requests(self, requests)
+    }
 }
 
 pub type TypedHandler = Arc<Box<dyn Handler + Send + Sync>>;
 
+/// Handles every request of a call on its own, answering the failed ones with the
+/// status and payload of their error.
+pub fn handle_requests<H>(handler: &H, requests: RequestStream) -> ResponseStream<'_>
+where
+    H: Handler + ?Sized,
+{
+    Box::pin(requests.then(move |mut request| async move {
+        let payload = std::mem::take(&mut request.payload);
+        match handler
+            .handle(Message {
+                payload,
+                ..request.clone()
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                debug!(?error, "Failed to handle a message");
+                Message::response(&request, error.status(), error.payload())
+            }
+        }
+    }))
+}
+
 #[derive(Clone)]
 pub struct SessionHandler {
     handler: TypedHandler,
@@ -109,10 +157,14 @@ impl SessionHandler {
                 let (mut send, mut recv) = quic::BidiStream::split(stream);
                 let handler = handler.clone();
 
+                // Each stream carries a single call, whose requests are read as they come
+                // while the handler answers them.
+                let (sender, receiver) = mpsc::channel(REQUEST_BUFFER);
                 tokio::spawn(async move {
                     loop {
                         let message = match read_message(&mut recv).await {
-                            Ok(message) => message,
+                            Ok(Some(message)) => message,
+                            Ok(None) => break,
                             Err(error) => {
                                 debug!(?error, "Failed to read a message");
                                 break;
@@ -121,16 +173,7 @@ impl SessionHandler {
 
                         match message.message_type {
                             MessageType::DataStream => {
-                                let response = match handler.handle(message).await {
-                                    Ok(response) => response,
-                                    Err(error) => {
-                                        debug!(?error, "Failed to handle a message");
-                                        continue;
-                                    }
-                                };
-
-                                if let Err(error) = write_message(response, &mut send).await {
-                                    debug!(?error, "Failed to write a response message");
+                                if sender.send(message).await.is_err() {
                                     break;
                                 }
                             }
@@ -141,43 +184,47 @@ impl SessionHandler {
                         }
                     }
                 });
+
+                tokio::spawn(async move {
+                    let requests = Box::pin(ReceiverStream::new(receiver));
+                    let mut responses = handler.handle_call(requests);
+                    while let Some(response) = responses.next().await {
+                        if let Err(error) = write_message(response, &mut send).await {
+                            debug!(?error, "Failed to write a response message");
+                            break;
+                        }
+                    }
+                });
             }
         }
     }
 }
 
+/// Reads the next message of a call, or `None` once the client finished its stream.
 async fn read_message(
     recv: &mut RecvStream<h3_quinn::RecvStream, Bytes>,
-) -> Result<Message, ServerError> {
+) -> Result<Option<Message>, ServerError> {
     let mut message_len_buffer = [0u8; 8];
     match recv.read_exact(&mut message_len_buffer).await {
         Ok(_) => (),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
         Err(error) => {
             error!(?error, "Failed to read the message length");
+            return Err(error.into());
         }
     };
 
     let message_len = u64::from_be_bytes(message_len_buffer);
-    let mut message_buffer = Vec::with_capacity(message_len as usize);
-
-    let mut total_bytes_read = 0usize;
-
-    loop {
-        if total_bytes_read >= message_len as usize {
-            break;
-        }
-
-        let mut temp_buffer = [0u8; 128];
-        let read_buf = match recv.read(&mut temp_buffer).await {
-            Ok(read_count) => read_count,
-            Err(error) => {
-                debug!(?error, "Failed to read from the stream");
-                break;
-            }
-        };
+    let mut message_buffer = Vec::new();
 
-        total_bytes_read += read_buf;
-        message_buffer.extend_from_slice(&temp_buffer[..read_buf]);
+    // Streams ending before the whole message was read fail rather than yield part of it.
+    let read_len = recv
+        .take(message_len)
+        .read_to_end(&mut message_buffer)
+        .await?;
+    if read_len as u64 != message_len {
+        debug!(read_len, message_len, "The stream ended within a message");
+        return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into());
     }
 
     let message = match ciborium::de::from_reader(message_buffer.as_slice()) {
@@ -188,7 +235,7 @@ async fn read_message(
         }
     };
 
-    Ok(message)
+    Ok(Some(message))
 }
 
 async fn write_message(
@@ -9,6 +9,7 @@ use std::time::Duration;
 use tracing::debug;
 
 pub mod config;
+pub mod dispatch;
 pub mod error;
 pub mod handler;
 
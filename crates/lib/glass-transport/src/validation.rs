@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use thiserror::Error;
+
+/// Regular expressions checked by `@pattern` constraints.
+pub use regex::Regex;
+
+/// Checks the constraints declared on a schema, e.g. `name: string @len(1..64);`.
+///
+/// Implemented by every generated schema and union, so requests can be validated before
+/// being dispatched to a handler, answering [Status::BadRequest](crate::message::status::Status::BadRequest)
+/// when they are not.
+///
+/// Every other type a generic schema may be given as an argument implements it as well,
+/// primitives having nothing to check and collections validating their items.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+macro_rules! impl_valid {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Validate for $ty {
+                fn validate(&self) -> Result<(), ValidationErrors> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_valid!(
+    String,
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    std::time::SystemTime,
+    std::time::Duration,
+    serde_bytes::ByteBuf,
+    ulid::Ulid,
+    uuid::Uuid,
+);
+
+impl<T: Validate + ?Sized> Validate for Box<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        (**self).validate()
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        for (index, item) in self.iter().enumerate() {
+            errors.nest(&format!("[{index}]"), item.validate());
+        }
+        errors.into_result()
+    }
+}
+
+impl<K: Debug, V: Validate, S> Validate for HashMap<K, V, S> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_entries(self.iter())
+    }
+}
+
+impl<K: Debug, V: Validate> Validate for BTreeMap<K, V> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_entries(self.iter())
+    }
+}
+
+fn validate_entries<'a, K: Debug + 'a, V: Validate + 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    for (key, value) in entries {
+        errors.nest(&format!("[{key:?}]"), value.validate());
+    }
+    errors.into_result()
+}
+
+/// Every constraint a value violates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Error)]
+pub struct ValidationErrors {
+    pub violations: Vec<Violation>,
+}
+
+/// A constraint violated by a field, whose path goes through nested schemas,
+/// e.g. `address.city` or `items[2].name`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationErrors {
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.violations.push(Violation {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Adds the violations of a nested value, prefixing their path with `field`.
+    pub fn nest(&mut self, field: &str, result: Result<(), ValidationErrors>) {
+        let Err(nested) = result else {
+            return;
+        };
+
+        // Items of collections are appended to the path as is, e.g. `items[2]`.
+        for violation in nested.violations {
+            let path = match violation.field.starts_with('[') {
+                true => format!("{field}{}", violation.field),
+                false => format!("{field}.{}", violation.field),
+            };
+            self.push(path, violation.message);
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        match self.violations.is_empty() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, violation) in self.violations.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{}` {}", violation.field, violation.message)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+//! Code generated for the snapshots of `glass-codegen`, built as a crate of its own so
+//! that a snapshot failing to compile fails the build of the workspace.
+//!
+//! Each file is a module, as laid out by `glc`. Snapshots are regenerated by running the
+//! tests of `glass-codegen` with `GLASS_UPDATE_SNAPSHOTS=1`, and left alone by `rustfmt`
+//! as they are compared with the output of the generator.
+
+#[rustfmt::skip]
+#[path = "../../../lib/glass-codegen/snapshots/declarations.rs"]
+pub mod declarations;
+#[rustfmt::skip]
+#[path = "../../../lib/glass-codegen/snapshots/numbered.rs"]
+pub mod numbered;
+#[rustfmt::skip]
+#[path = "../../../lib/glass-codegen/snapshots/services.rs"]
+pub mod services;
+
+#[cfg(test)]
+mod tests {
+    use crate::declarations::acme::store::{
+        Album, Archive, Attachment, Book, Catalog, Genre, Item, Page, Shelf,
+    };
+    use crate::numbered::User;
+    use crate::services::acme::store::{
+        Health, Order, Orders, OrdersClient, OrdersPlaceError, OrdersServer, OutOfStock,
+    };
+    use async_trait::async_trait;
+    use futures::StreamExt;
+    use glass_transport::client::Client;
+    use glass_transport::client::config::{
+        ChannelConfig, ChannelHttpConfig, ChannelSecurityConfig,
+    };
+    use glass_transport::client::error::{CallError, ClientError};
+    use glass_transport::client::local::LocalChannel;
+    use glass_transport::client::webtransport::WebTransportChannel;
+    use glass_transport::message::Message;
+    use glass_transport::message::status::Status;
+    use glass_transport::server::Server;
+    use glass_transport::server::config::{ServerConfig, ServerHttpConfig, ServerSecurityConfig};
+    use glass_transport::server::dispatch::Requests;
+    use glass_transport::server::error::ServerError;
+    use glass_transport::server::handler::Handler;
+    use glass_transport::validation::Validate;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::net::UdpSocket;
+    use std::path::Path;
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    struct Store;
+
+    #[async_trait]
+    impl Health for Store {
+        type Error = String;
+
+        async fn ping(&self) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    #[allow(deprecated)]
+    impl Orders for Store {
+        type Error = String;
+        type InputStream<T>
+            = Requests<T>
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
+        type OutputStream<T>
+            = Pin<Box<dyn glass_transport::Stream<Item = T> + Send + Sync>>
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync;
+
+        async fn place(
+            &self,
+            item: String,
+            quantity: u32,
+        ) -> Result<Order, OrdersPlaceError<String>> {
+            match quantity {
+                0 => Err(OutOfStock { item }.into()),
+                quantity => Ok(Order {
+                    id: 1,
+                    total: quantity as u64,
+                }),
+            }
+        }
+
+        async fn get(&self, request: u64) -> Result<Order, String> {
+            Ok(Order {
+                id: request,
+                total: 0,
+            })
+        }
+
+        async fn list(&self, request: Vec<u64>) -> Result<Vec<Order>, String> {
+            Ok(request
+                .into_iter()
+                .map(|id| Order { id, total: 0 })
+                .collect())
+        }
+
+        async fn restock(&self, request: Vec<Order>) -> Result<u64, String> {
+            Ok(request.iter().map(|order| order.total).sum())
+        }
+
+        async fn watch(&self, request: u64) -> Result<Self::OutputStream<Order>, String> {
+            let orders = (0..request).map(move |total| Order { id: request, total });
+            Ok(Box::pin(futures::stream::iter(orders)))
+        }
+
+        async fn upload(&self, request: Self::InputStream<Order>) -> Result<u64, String> {
+            Ok(request
+                .fold(0, |total, order| async move { total + order.total })
+                .await)
+        }
+
+        async fn sync(
+            &self,
+            request: Self::InputStream<Order>,
+        ) -> Result<Self::OutputStream<Order>, String> {
+            Ok(Box::pin(request))
+        }
+    }
+
+    fn client() -> OrdersClient<String> {
+        let server = OrdersServer::new(Store);
+        OrdersClient::new(Client::new(LocalChannel::new(Arc::new(Box::new(server)))))
+    }
+
+    #[test]
+    fn test_derives() {
+        let book = Book {
+            title: "Dune".to_string(),
+            genre: Genre::Fiction,
+            pages: Some(412),
+        };
+        let books = HashSet::from([book.clone(), book.clone()]);
+        assert_eq!(books.len(), 1);
+
+        let album = Item::Album(Album {
+            id: ulid::Ulid::nil(),
+            tracks: vec!["Intro".to_string()],
+            rating: Some(4.5),
+        });
+        assert_eq!(album.clone(), album);
+
+        let user = User {
+            id: 1,
+            nickname: None,
+            labels: BTreeMap::new(),
+        };
+        assert_eq!(HashSet::from([user.clone(), user]).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_type_arguments() {
+        let book = |title: &str| {
+            Item::Book(Book {
+                title: title.to_string(),
+                genre: Genre::Poetry,
+                pages: None,
+            })
+        };
+        let shelf = Shelf {
+            items: Page {
+                items: vec![book("Odes"), book("")],
+                size: 2,
+            },
+            next: None,
+            labels: HashMap::new(),
+        };
+
+        let errors = shelf.validate().unwrap_err();
+        assert_eq!(errors.violations.len(), 1);
+        assert_eq!(errors.violations[0].field, "items.items[1].title");
+    }
+
+    #[test]
+    fn test_well_known_types() {
+        let attachment = Attachment {
+            id: uuid::Uuid::nil(),
+            content: serde_bytes::ByteBuf::new(),
+            uploaded_at: std::time::SystemTime::UNIX_EPOCH,
+            retention: Some(std::time::Duration::from_secs(60)),
+        };
+        assert_eq!(
+            HashSet::from([attachment.clone(), attachment.clone()]).len(),
+            1
+        );
+
+        let archive = Archive {
+            catalog: Catalog {
+                counts: HashMap::from([(Genre::Poetry, 1)]),
+                attachments: HashMap::from([(attachment.id, attachment.clone())]),
+                checksums: HashMap::from([(attachment.content, attachment.uploaded_at)]),
+            },
+            archived_at: std::time::SystemTime::UNIX_EPOCH,
+        };
+        assert_eq!(archive.clone(), archive);
+
+        let errors = archive.validate().unwrap_err();
+        assert_eq!(errors.violations.len(), 1);
+        assert_eq!(
+            errors.violations[0].field,
+            format!("catalog.attachments[{:?}].content", uuid::Uuid::nil())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calls() {
+        let orders = client();
+        orders.ping().await.unwrap();
+        assert_eq!(orders.get(7).await.unwrap(), Order { id: 7, total: 0 });
+        assert_eq!(orders.list(vec![1, 2]).await.unwrap().len(), 2);
+        assert_eq!(orders.place("book".to_string(), 3).await.unwrap().total, 3);
+        assert!(matches!(
+            orders.place("book".to_string(), 0).await,
+            Err(CallError::Service(OrdersPlaceError::OutOfStock(_)))
+        ));
+    }
+
+    /// Handler failing every request on its own, as a server does when it can't answer them.
+    struct Failing;
+
+    #[async_trait]
+    impl Handler for Failing {
+        async fn handle(&self, _message: Message) -> Result<Message, ServerError> {
+            Err(ServerError::Status(Status::Internal))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_internal_failure() {
+        let orders: OrdersClient<String> =
+            OrdersClient::new(Client::new(LocalChannel::new(Arc::new(Box::new(Failing)))));
+        assert!(matches!(
+            orders.place("book".to_string(), 3).await,
+            Err(CallError::Client(ClientError::Status(Status::Internal)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request() {
+        let orders = client();
+        let Err(CallError::Client(ClientError::InvalidRequest(errors))) =
+            orders.place(String::new(), 1).await
+        else {
+            panic!("Expected an invalid request");
+        };
+        assert_eq!(errors.violations.len(), 1);
+        assert_eq!(errors.violations[0].field, "item");
+
+        let restocked = vec![Order { id: 1, total: 2 }, Order { id: 2, total: 1000 }];
+        let Err(CallError::Client(ClientError::InvalidRequest(errors))) =
+            orders.restock(restocked).await
+        else {
+            panic!("Expected an invalid request");
+        };
+        assert_eq!(errors.violations.len(), 1);
+        assert_eq!(errors.violations[0].field, "[1].total");
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_streaming_calls() {
+        let orders = client();
+        let watched = orders.watch(3).await.unwrap();
+        assert_eq!(watched.count().await, 3);
+
+        let uploaded = [Order { id: 1, total: 2 }, Order { id: 2, total: 3 }];
+        let total = orders.upload(futures::stream::iter(uploaded.clone()));
+        assert_eq!(total.await.unwrap(), 5);
+
+        let synced = orders.sync(futures::stream::iter(uploaded)).await.unwrap();
+        let synced = synced.map(|order| order.unwrap().id).collect::<Vec<_>>();
+        assert_eq!(synced.await, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_invalid_streamed_request() {
+        let orders = client();
+        let valid = Order { id: 1, total: 2 };
+        let invalid = Order { id: 2, total: 1000 };
+
+        // Invalid requests fail the call, whether they come first or later on.
+        for uploaded in [
+            [invalid.clone(), valid.clone()],
+            [valid.clone(), invalid.clone()],
+        ] {
+            let Err(CallError::Client(ClientError::InvalidRequest(errors))) =
+                orders.upload(futures::stream::iter(uploaded)).await
+            else {
+                panic!("Expected an invalid request");
+            };
+            assert_eq!(errors.violations[0].field, "total");
+        }
+
+        let synced = orders.sync(futures::stream::iter([valid, invalid])).await;
+        let synced = synced.unwrap().collect::<Vec<_>>().await;
+        assert_eq!(synced.len(), 2);
+        assert_eq!(synced[0].as_ref().unwrap().id, 1);
+        assert!(matches!(
+            synced[1],
+            Err(CallError::Client(ClientError::InvalidRequest(_)))
+        ));
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_webtransport_calls() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        // The port is picked by the system, then released for the server to bind it.
+        let server_address = UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let tls = Path::new(env!("CARGO_MANIFEST_DIR")).join("tls");
+        let server_config = ServerConfig {
+            http: ServerHttpConfig {
+                bind_address: server_address,
+            },
+            security: ServerSecurityConfig {
+                tls_certificate: tls.join("certificate.der"),
+                tls_private_key: tls.join("key.der"),
+            },
+        };
+        tokio::spawn(async move {
+            Server::serve(&server_config, Arc::new(Box::new(OrdersServer::new(Store)))).await
+        });
+
+        let channel = WebTransportChannel::connect(&ChannelConfig {
+            http: ChannelHttpConfig {
+                server_address,
+                server_name: "localhost".to_string(),
+            },
+            security: ChannelSecurityConfig {
+                tls_certificate: tls.join("certificate.der"),
+            },
+        })
+        .await
+        .unwrap();
+        let orders = OrdersClient::<String>::new(Client::new(channel));
+
+        orders.ping().await.unwrap();
+        assert_eq!(orders.get(7).await.unwrap(), Order { id: 7, total: 0 });
+        assert!(matches!(
+            orders.place("book".to_string(), 0).await,
+            Err(CallError::Service(OrdersPlaceError::OutOfStock(_)))
+        ));
+
+        let watched = orders.watch(3).await.unwrap();
+        assert_eq!(watched.count().await, 3);
+
+        let uploaded = [Order { id: 1, total: 2 }, Order { id: 2, total: 3 }];
+        let synced = orders.sync(futures::stream::iter(uploaded)).await.unwrap();
+        let synced = synced.map(|order| order.unwrap().id).collect::<Vec<_>>();
+        assert_eq!(synced.await, vec![1, 2]);
+    }
+}